@@ -1,5 +1,6 @@
 use thiserror::Error;
-use std::io::Cursor;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
 
 use crate::ppc;
 
@@ -24,463 +25,5912 @@ pub enum GeckoCodeConversionError {
     #[error("Failed to parse gecko code. {reason}")]
     ParseError {
         reason: String
+    },
+
+    #[error("Gecko code does not follow the documented format. Line number: {line_number}. {reason}")]
+    SpecViolation {
+        line_number: usize,
+        reason: String
+    },
+
+    #[error("Code index out of range. Index: {index}, number of codes: {count}")]
+    IndexOutOfRange {
+        index: usize,
+        count: usize
+    },
+
+    #[error("Gecko code type has no Action Replay equivalent. Line number: {line_number}, code type: 0x{:02X}", code_type)]
+    NoActionReplayEquivalent {
+        line_number: usize,
+        code_type: u8
+    },
+
+    #[error("Gecko code ends more conditionals than are open. Line number: {line_number}")]
+    UnbalancedBlock {
+        line_number: usize
+    },
+
+    #[error("Gecko code is cut off. Line number: {line_number}, code type: 0x{:02X}", opcode)]
+    Truncated {
+        line_number: usize,
+        opcode: u8
+    },
+
+    #[error("Address can't be reached from the default base address. Address: 0x{:08X}", address)]
+    UnreachableAddress {
+        address: u32
+    }
+}
+
+impl GeckoCodeConversionError {
+    /// Returns the line number that the error happened at, if it's known.
+    pub fn line_number(&self) -> Option<usize> {
+        match self {
+            GeckoCodeConversionError::InvalidType { line_number, .. }
+            | GeckoCodeConversionError::SpecViolation { line_number, .. }
+            | GeckoCodeConversionError::NoActionReplayEquivalent { line_number, .. }
+            | GeckoCodeConversionError::UnbalancedBlock { line_number }
+            | GeckoCodeConversionError::Truncated { line_number, .. } => Some(*line_number),
+
+            _ => None
+        }
+    }
+}
+
+/// Returns the lines within `radius` lines of `line_number` (starting from 1)
+/// as hex, with the line itself marked, to show where an error happened.
+pub fn error_context(gecko_code: &[u32], line_number: usize, radius: usize) -> String {
+    let lines = gecko_code.chunks(2).collect::<Vec<&[u32]>>();
+
+    let first = line_number.saturating_sub(radius).max(1);
+    let last = (line_number + radius).min(lines.len());
+
+    let mut result = String::new();
+
+    for number in first..=last {
+        let marker = if number == line_number { ">" } else { " " };
+
+        let words = lines[number - 1]
+            .iter()
+            .map(|value| format!("{:08X}", value))
+            .collect::<Vec<String>>();
+
+        result += &format!("{marker} {number:>4} | {}\n", words.join(" "));
+    }
+
+    result
+}
+
+/// Returns the number of values that a code of type `code_type` needs to be read.
+fn minimum_code_length(code_type: u8) -> usize {
+    match code_type {
+        // Serial RAM Write
+        0x08 | 0x09 => 4,
+
+        _ => 2
+    }
+}
+
+/// Returns the address encoded in the first value of a conditional code.
+/// The lowest bit of the address is the "end the previous if first" flag, so it's ignored.
+/// For a `po`-relative (`0x3X`) conditional, this is the offset from `po`.
+fn conditional_address(value: u32, pointer: bool) -> u32 {
+    let larger_address = value & 0x01000000 != 0;
+    let offset = (value & 0x00FFFFFE) | if larger_address { 0x01000000 } else { 0 };
+
+    if pointer {
+        offset
+    } else {
+        0x80000000 + offset
+    }
+}
+
+/// Returns the address encoded in the first value of a code.
+///
+/// The address is an offset from the base address, which is `0x80000000`
+/// unless a code changes it. The offset is 25 bits wide, and its highest bit
+/// is the `larger_address` flag, so it only spans `0x80000000..0x82000000`.
+/// MEM2 (`0x90000000` and up) can only be reached by changing the base address.
+fn code_address(value: u32, larger_address: bool) -> u32 {
+    let offset = value & 0x00FFFFFF;
+
+    if larger_address {
+        0x80000000 + (offset | 0x01000000)
+    } else {
+        0x80000000 + offset
+    }
+}
+
+
+/// A single converted code within a gecko code list.
+#[derive(Debug, Clone)]
+pub struct ConvertedCode {
+    /// The line number that the code starts on.
+    pub line_number: usize,
+    /// The code type byte.
+    pub code_type: u8,
+    /// The range of values that make up the code.
+    pub span: Range<usize>,
+    /// The address that the code primarily targets, if it has one.
+    pub address: Option<u32>,
+    /// The number of conditionals that the code is inside of.
+    pub depth: usize,
+    /// The converted code, indented by two spaces for each conditional it's inside of.
+    pub text: String
+}
+
+// This is NOT a conclusive list of every type of gecko code.
+// Instead, it consists of commonly-used types.
+// Feel free to request that a code type be implemented.
+
+/// A list of supported code type bytes, their short names, and their names.
+pub const SUPPORTED_TYPES: [(u8, &str, &str); 81] = [
+    (0x00, "fill8", "8-bit RAM Write & Fill"),
+    (0x01, "fill8", "8-bit RAM Write & Fill"),
+    (0x02, "fill16", "16-bit RAM Write & Fill"),
+    (0x03, "fill16", "16-bit RAM Write & Fill"),
+    (0x04, "write32", "32-bit RAM Write"),
+    (0x05, "write32", "32-bit RAM Write"),
+    (0x06, "string", "String RAM Write"),
+    (0x07, "string", "String RAM Write"),
+    (0x08, "serial", "Serial RAM Write"),
+    (0x09, "serial", "Serial RAM Write"),
+    (0x20, "if_eq32", "32-bit If Equal"),
+    (0x21, "if_eq32", "32-bit If Equal"),
+    (0x22, "if_ne32", "32-bit If Not Equal"),
+    (0x23, "if_ne32", "32-bit If Not Equal"),
+    (0x24, "if_gt32", "32-bit If Greater Than"),
+    (0x25, "if_gt32", "32-bit If Greater Than"),
+    (0x26, "if_lt32", "32-bit If Lower Than"),
+    (0x27, "if_lt32", "32-bit If Lower Than"),
+    (0x28, "if_eq16", "16-bit If Equal"),
+    (0x29, "if_eq16", "16-bit If Equal"),
+    (0x2A, "if_ne16", "16-bit If Not Equal"),
+    (0x2B, "if_ne16", "16-bit If Not Equal"),
+    (0x2C, "if_gt16", "16-bit If Greater Than"),
+    (0x2D, "if_gt16", "16-bit If Greater Than"),
+    (0x2E, "if_lt16", "16-bit If Lower Than"),
+    (0x2F, "if_lt16", "16-bit If Lower Than"),
+    (0x30, "if_eq32", "32-bit If Equal"),
+    (0x31, "if_eq32", "32-bit If Equal"),
+    (0x32, "if_ne32", "32-bit If Not Equal"),
+    (0x33, "if_ne32", "32-bit If Not Equal"),
+    (0x34, "if_gt32", "32-bit If Greater Than"),
+    (0x35, "if_gt32", "32-bit If Greater Than"),
+    (0x36, "if_lt32", "32-bit If Lower Than"),
+    (0x37, "if_lt32", "32-bit If Lower Than"),
+    (0x38, "if_eq16", "16-bit If Equal"),
+    (0x39, "if_eq16", "16-bit If Equal"),
+    (0x3A, "if_ne16", "16-bit If Not Equal"),
+    (0x3B, "if_ne16", "16-bit If Not Equal"),
+    (0x3C, "if_gt16", "16-bit If Greater Than"),
+    (0x3D, "if_gt16", "16-bit If Greater Than"),
+    (0x3E, "if_lt16", "16-bit If Lower Than"),
+    (0x3F, "if_lt16", "16-bit If Lower Than"),
+    (0x40, "load_ba", "Load into Base Address"),
+    (0x42, "set_ba", "Set Base Address to"),
+    (0x44, "store_ba", "Store Base Address at"),
+    (0x46, "ba_location", "Put Code Location into Base Address"),
+    (0x48, "load_po", "Load into Pointer Offset"),
+    (0x4A, "set_po", "Set Pointer Offset to"),
+    (0x4C, "store_po", "Store Pointer Offset at"),
+    (0x4E, "po_location", "Put Code Location into Pointer Offset"),
+    (0x50, "load_ba", "Load into Base Address"),
+    (0x52, "set_ba", "Set Base Address to"),
+    (0x54, "store_ba", "Store Base Address at"),
+    (0x58, "load_po", "Load into Pointer Offset"),
+    (0x5A, "set_po", "Set Pointer Offset to"),
+    (0x5C, "store_po", "Store Pointer Offset at"),
+    (0x60, "set_repeat", "Set Repeat"),
+    (0x62, "execute_repeat", "Execute Repeat"),
+    (0x64, "return", "Return"),
+    (0x66, "goto", "Goto"),
+    (0x68, "gosub", "Gosub"),
+    (0x80, "set_gr", "Set Gecko Register to"),
+    (0x82, "load_gr", "Load into Gecko Register"),
+    (0x84, "store_gr", "Store Gecko Register at"),
+    (0x86, "gr_op", "Gecko Register Operation"),
+    (0x88, "gr_op", "Gecko Register Operation"),
+    (0x8A, "memcpy", "Memory Copy"),
+    (0x8C, "memcpy", "Memory Copy"),
+    (0x90, "set_gr", "Set Gecko Register to"),
+    (0x94, "store_gr", "Store Gecko Register at"),
+    (0xA8, "if_counter_eq", "16-bit If Counter Equal"),
+    (0xAA, "if_counter_ne", "16-bit If Counter Not Equal"),
+    (0xAC, "if_counter_gt", "16-bit If Counter Greater Than"),
+    (0xAE, "if_counter_lt", "16-bit If Counter Lower Than"),
+    (0xC0, "execute_asm", "Execute Assembly"),
+    (0xC2, "insert_asm", "Insert Assembly"),
+    (0xC3, "insert_asm", "Insert Assembly"),
+    (0xC6, "branch", "Create a Branch"),
+    (0xC7, "branch", "Create a Branch"),
+    (0xE0, "full_terminator", "Full Terminator"),
+    (0xE2, "endif", "Endif")
+];
+
+/// Options that control how a gecko code is converted.
+#[derive(Debug, Clone, Default)]
+pub struct ConversionOptions {
+    /// Prepend a table of contents listing each code.
+    pub table_of_contents: bool,
+    /// Only include codes whose target address falls within this range.
+    /// Codes outside of the range are shown as `// [filtered]`.
+    pub only_addresses: Option<Range<u32>>,
+    /// Wrap comment lines that are longer than this many columns.
+    pub max_columns: Option<usize>,
+    /// Separate every this many bytes of a byte dump with an extra space.
+    pub byte_group_size: Option<usize>,
+    /// Reject codes that don't strictly follow the documented format,
+    /// instead of converting them anyway.
+    pub strict: bool,
+    /// Titles to show above codes, such as `// [Infinite Lives]`.
+    pub code_titles: HashMap<CodeTitleKey, String>,
+    /// Show a hash of each code's values, to help find identical codes.
+    pub content_hash: bool,
+    /// Show how far the address of each write is from the address of the previous write.
+    pub address_delta: bool,
+    /// Show the values that each code was converted from.
+    pub echo_source: bool,
+    /// When showing the values of each code, also show them in little-endian byte order.
+    pub echo_little_endian: bool,
+    /// Number the instructions of each assembly block, starting from 1 in every block.
+    pub number_instructions: bool,
+    /// Warn about conditionals that can never be true inside of the conditionals they're in.
+    pub unreachable_warnings: bool,
+    /// Show a rough estimate of how many cycles each instruction of an assembly block takes, and their total.
+    pub cycle_estimates: bool,
+    /// Replace the standard LR-restore epilogue at the end of an Insert Assembly block,
+    /// along with the note about branching back, with a single note.
+    pub collapse_epilogue: bool
+}
+
+/// What's carried over from one converted code to the next, so that
+/// a gecko code can be converted a piece at a time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConversionState {
+    /// The number of conditionals that the next code is inside of.
+    pub depth: usize,
+    /// If a code may have moved the base address away from `0x80000000`.
+    pub base_address_changed: bool
+}
+
+/// Identifies the code that a title belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CodeTitleKey {
+    /// The line number that the code starts on.
+    Line(usize),
+    /// The address that the code primarily targets.
+    Address(u32)
+}
+
+/// Returns the title of the code starting on `line_number` and targeting `address`, if it has one.
+fn code_title(titles: &HashMap<CodeTitleKey, String>, line_number: usize, address: Option<u32>) -> Option<&String> {
+    // a title for the line takes priority over one for the address
+    titles
+        .get(&CodeTitleKey::Line(line_number))
+        .or_else(|| address.and_then(|address| titles.get(&CodeTitleKey::Address(address))))
+}
+
+/// Splits a gecko code into separate named codes. A new code starts at
+/// each code with a title, and the codes following it without one are
+/// part of it. Any codes before the first title are named `Unnamed`.
+/// ## Returns
+/// The name and values of each code, in order.
+pub fn split_named_codes(gecko_code: &[u32], titles: &HashMap<CodeTitleKey, String>) -> Result<Vec<(String, Vec<u32>)>, GeckoCodeConversionError> {
+    let mut result: Vec<(String, Vec<u32>)> = Vec::new();
+
+    for code in convert_codes(gecko_code)? {
+        let values = &gecko_code[code.span.clone()];
+
+        match (code_title(titles, code.line_number, code.address), result.last_mut()) {
+            (None, Some((_, named_values))) => named_values.extend(values),
+            (title, _) => result.push((title.map_or("Unnamed", String::as_str).to_string(), values.to_vec()))
+        }
+    }
+
+    Ok(result)
+}
+
+/// Parses a file of code titles. Each line is a key followed by the title,
+/// where the key is either a line number or an address starting with `0x`:
+/// ```text
+/// 1 Infinite Lives
+/// 0x8016B1C8 Moon Jump
+/// ```
+/// Blank lines and lines starting with `#` are skipped.
+pub fn parse_code_titles(input: &str) -> Result<HashMap<CodeTitleKey, String>, GeckoCodeConversionError> {
+    let mut titles = HashMap::new();
+
+    for line in input.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (key, title) = line.split_once(char::is_whitespace).ok_or_else(|| GeckoCodeConversionError::ParseError {
+            reason: format!("Missing title for \"{line}\"")
+        })?;
+
+        let key = match key.strip_prefix("0x") {
+            Some(address) => u32::from_str_radix(address, 16).map(CodeTitleKey::Address).ok(),
+            None => key.parse().map(CodeTitleKey::Line).ok()
+        };
+
+        let Some(key) = key else {
+            return Err(GeckoCodeConversionError::ParseError {
+                reason: format!("Invalid code title key in \"{line}\"")
+            });
+        };
+
+        titles.insert(key, title.trim().to_string());
+    }
+
+    Ok(titles)
+}
+
+/// Parses the `[Gecko]` section of a Dolphin game INI (`GameID.ini`). A
+/// code starts at each line beginning with `$`, which holds its name, and
+/// consists of the lines following it. Note lines starting with `*` and
+/// every other section are skipped:
+/// ```text
+/// [Gecko]
+/// $Infinite Lives [Author]
+/// 04001000 00000063
+/// *Keeps the lives counter full.
+/// ```
+/// ## Returns
+/// The name and text of each code, in order.
+pub fn parse_dolphin_ini(input: &str) -> Vec<(String, String)> {
+    let mut codes: Vec<(String, String)> = Vec::new();
+    let mut in_gecko_section = false;
+
+    for line in input.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_gecko_section = line.eq_ignore_ascii_case("[Gecko]");
+            continue;
+        }
+
+        if !in_gecko_section || line.is_empty() || line.starts_with('*') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('$') {
+            codes.push((name.trim().to_string(), String::new()));
+        } else if let Some((_, text)) = codes.last_mut() {
+            // lines before the first name don't belong to a code
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    codes
+}
+
+/// Checks every code in the `[Gecko]` section of a Dolphin game INI,
+/// without keeping their conversions.
+/// ## Returns
+/// The name of each code that couldn't be converted, along with why.
+pub fn validate_dolphin_ini(input: &str) -> Vec<(String, GeckoCodeConversionError)> {
+    parse_dolphin_ini(input)
+        .into_iter()
+        .filter_map(|(name, text)| {
+            let result = parse_gecko_text(&text).and_then(|values| convert_codes(&values));
+            result.err().map(|err| (name, err))
+        })
+        .collect()
+}
+
+/// Returns the name of a supported code type.
+pub fn code_type_name(code_type: u8) -> Option<&'static str> {
+    SUPPORTED_TYPES
+        .iter()
+        .find(|(byte, _, _)| *byte == code_type)
+        .map(|(_, _, name)| *name)
+}
+
+/// Returns the short name of a supported code type.
+pub fn code_type_short_name(code_type: u8) -> Option<&'static str> {
+    SUPPORTED_TYPES
+        .iter()
+        .find(|(byte, _, _)| *byte == code_type)
+        .map(|(_, short_name, _)| *short_name)
+}
+
+/// The kind of thing a code type does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CodeCategory {
+    /// Writes to memory (`0x0X`).
+    Write,
+    /// Only runs the following codes if a condition is true (`0x2X`, `0x3X`, `0xAX`).
+    Conditional,
+    /// Changes the base address or pointer offset (`0x4X`, `0x5X`).
+    Address,
+    /// Works with the gecko registers (`0x8X`, `0x9X`).
+    Register,
+    /// Runs or inserts assembly (`0xCX`).
+    Assembly,
+    /// Anything else.
+    Other
+}
+
+/// Returns the category of a code type.
+pub fn code_category(code_type: u8) -> CodeCategory {
+    match code_type {
+        0x00..=0x0F => CodeCategory::Write,
+        0x20..=0x3F | 0xA0..=0xAF => CodeCategory::Conditional,
+        0x40..=0x5F => CodeCategory::Address,
+        0x80..=0x9F => CodeCategory::Register,
+        0xC0..=0xCF => CodeCategory::Assembly,
+        _ => CodeCategory::Other
+    }
+}
+
+/// Converts a gecko code into a list of individually converted codes.
+pub fn convert_codes(gecko_code: &[u32]) -> Result<Vec<ConvertedCode>, GeckoCodeConversionError> {
+    convert_codes_with_options(gecko_code, &ConversionOptions::default())
+}
+
+/// Converts a gecko code into a list of individually converted codes using the given `options`.
+pub fn convert_codes_with_options(gecko_code: &[u32], options: &ConversionOptions) -> Result<Vec<ConvertedCode>, GeckoCodeConversionError> {
+    convert_codes_continuing(gecko_code, options, &mut ConversionState::default())
+}
+
+/// Converts a gecko code that follows the codes `state` was last used with,
+/// such as the conditionals they left open. `state` is updated for the codes that follow.
+/// Line numbers are still counted from the start of `gecko_code`.
+pub fn convert_codes_continuing(gecko_code: &[u32], options: &ConversionOptions, state: &mut ConversionState) -> Result<Vec<ConvertedCode>, GeckoCodeConversionError> {
+    let code_length = gecko_code.len();
+
+    if code_length == 0 {
+        return Err(GeckoCodeConversionError::Empty);
+    }
+
+    let mut codes: Vec<ConvertedCode> = Vec::new();
+
+    let mut previous_write_address: Option<u32> = None;
+
+    // the line that each block's repeat jumps back to
+    let mut repeat_targets: [Option<usize>; 16] = [None; 16];
+
+    // the number of conditionals that the current code is inside of
+    let mut depth = state.depth;
+
+    // once the base address has moved, the addresses relative to it aren't known
+    let mut base_address_changed = state.base_address_changed;
+
+    let mut current_cursor_position = 0;
+    while current_cursor_position < gecko_code.len() {
+        let current_value = gecko_code[current_cursor_position];
+
+        // detect code type -- this is the first byte in the code sequence
+        let byte = ((current_value & 0xFF000000) >> 0x18) as u8;
+
+        // make sure the code is valid length-wise
+        if current_cursor_position + minimum_code_length(byte) > code_length {
+            let err = GeckoCodeConversionError::Truncated {
+                // the last line is the one that's short
+                line_number: (code_length / 2) + 1,
+                opcode: byte
+            };
+
+            return Err(err);
+        }
+
+        let strict_end = if options.strict {
+            check_strict(gecko_code, current_cursor_position, byte)?
+        } else {
+            None
+        };
+
+        let (code, num_values) = GeckoCode::parse(&gecko_code[current_cursor_position..])
+            .map_err(|err| offset_line_number(err, current_cursor_position / 2))?;
+
+        let next_cursor_position = current_cursor_position + num_values;
+        let next_line_number = (next_cursor_position / 2) + 1;
+
+        let text = convert_code(&code, next_line_number, base_address_changed, &mut repeat_targets, options)?;
+
+        let text = match options.max_columns {
+            Some(max_columns) => wrap_comment_lines(&text, max_columns),
+            None => text
+        };
+
+        // the code must end exactly where it says it does
+        if strict_end.is_some_and(|end| end != next_cursor_position) {
+            let err = GeckoCodeConversionError::SpecViolation {
+                line_number: (current_cursor_position / 2) + 1,
+                reason: "The code does not end at its declared line count.".to_string()
+            };
+
+            return Err(err);
+        }
+
+        let span = current_cursor_position..next_cursor_position;
+
+        let line_number = (current_cursor_position / 2) + 1;
+
+        let address = if base_address_changed && is_base_address_relative(&code) {
+            None
+        } else {
+            code.address()
+        };
+
+        base_address_changed = moves_base_address(&code, base_address_changed);
+
+        // conditionals nest the codes following them, until they're ended
+        let code_depth = match code {
+            GeckoCode::If32 { endif_first, .. }
+            | GeckoCode::If16 { endif_first, .. }
+            | GeckoCode::IfCounter { endif_first, .. } => {
+                if endif_first {
+                    depth = depth.saturating_sub(1);
+                }
+
+                depth += 1;
+                depth - 1
+            }
+
+            GeckoCode::FullTerminator { .. } => {
+                depth = 0;
+                depth
+            }
+
+            GeckoCode::Endif { is_else, count, .. } => {
+                // an else ends the last conditional and starts another
+                let ended = (count as usize).max(is_else as usize);
+
+                if ended > depth {
+                    return Err(GeckoCodeConversionError::UnbalancedBlock { line_number });
+                }
+
+                depth = depth - ended + is_else as usize;
+                depth - is_else as usize
+            }
+
+            _ => depth
+        };
+
+        let is_write = match code {
+            GeckoCode::Fill8 { .. }
+            | GeckoCode::Fill16 { .. }
+            | GeckoCode::Write32 { .. }
+            | GeckoCode::StringWrite { .. }
+            | GeckoCode::SerialWrite { .. } => true,
+
+            // only when storing to an absolute address
+            GeckoCode::StoreGeckoRegister { address_type, .. } => address_type == 0,
+
+            _ => false
+        };
+
+        let text = match (is_write.then_some(address).flatten(), previous_write_address) {
+            (Some(address), Some(previous)) if options.address_delta => {
+                let delta = address as i64 - previous as i64;
+                let sign = if delta < 0 { "-" } else { "+" };
+
+                format!("// Delta: {sign}0x{:X}\n{text}", delta.abs())
+            }
+
+            _ => text
+        };
+
+        if is_write {
+            previous_write_address = address;
+        }
+
+        let text = if options.echo_source {
+            format!("{}{text}", source_echo(&gecko_code[span.clone()], options.echo_little_endian))
+        } else {
+            text
+        };
+
+        let text = if options.content_hash {
+            format!("// Hash: {:08x}\n{text}", content_hash(&gecko_code[span.clone()]))
+        } else {
+            text
+        };
+
+        let text = match code_title(&options.code_titles, line_number, address) {
+            Some(title) => format!("// [{title}]\n{text}"),
+            None => text
+        };
+
+        let text = indent_lines(&text, code_depth);
+
+        codes.push(ConvertedCode {
+            line_number,
+            code_type: byte,
+            address,
+            span,
+            depth: code_depth,
+            text
+        });
+
+        current_cursor_position = next_cursor_position;
+    }
+
+    state.depth = depth;
+    state.base_address_changed = base_address_changed;
+
+    // the warnings are indented the same as the code they're added to
+    if options.unreachable_warnings {
+        for index in unreachable_conditionals(gecko_code, &codes) {
+            let warning = indent_lines("// WARNING: guarded code is unreachable.", codes[index].depth);
+            codes[index].text += &format!("\n{warning}");
+        }
+    }
+
+    if let Some(index) = cyclic_jump(gecko_code, &codes) {
+        let warning = indent_lines("// WARNING: this jumps back in a cycle that never runs out.", codes[index].depth);
+        codes[index].text += &format!("\n{warning}");
+    }
+
+    Ok(codes)
+}
+
+/// Returns `true` if the address of `code` is an offset from the base address,
+/// so it's only known while the base address hasn't moved from `0x80000000`.
+fn is_base_address_relative(code: &GeckoCode) -> bool {
+    matches!(
+        code,
+        GeckoCode::Fill8 { .. }
+            | GeckoCode::Fill16 { .. }
+            | GeckoCode::Write32 { .. }
+            | GeckoCode::StringWrite { .. }
+            | GeckoCode::SerialWrite { .. }
+            | GeckoCode::If32 { pointer: false, .. }
+            | GeckoCode::If16 { pointer: false, .. }
+    )
+}
+
+/// Returns `true` if the base address may have moved away from `0x80000000`
+/// after `code`, given `base_address_changed`, which is if it may have before.
+fn moves_base_address(code: &GeckoCode, base_address_changed: bool) -> bool {
+    match code {
+        // loading into, setting, or putting the code location into the base address
+        GeckoCode::AddressOperation { code_type: 0x40 | 0x42 | 0x46 | 0x50 | 0x52, .. } => true,
+
+        // the terminators can set it back, or to somewhere else
+        GeckoCode::FullTerminator { base_address, .. }
+        | GeckoCode::Endif { base_address, .. } => match base_address {
+            0 => base_address_changed,
+            base_address => *base_address != 0x8000
+        },
+
+        _ => base_address_changed
+    }
+}
+
+/// Returns how the address of a write or conditional is shown, which
+/// is relative to `ba` once it may have moved from where it starts.
+fn target_address(address: u32, base_address_changed: bool) -> String {
+    if base_address_changed {
+        format!("(ba + 0x{:X})", address - 0x80000000)
+    } else {
+        format!("0x{:08X}", address)
+    }
+}
+
+/// Converts a single code into written lines.
+/// ## Parameters
+/// `code`: The code to convert.
+/// `next_line_number`: The line of the code list that follows the code.
+/// `base_address_changed`: Indicates if the addresses relative to `ba` aren't known.
+/// `repeat_targets`: The line that each block's repeat jumps back to.
+/// `options`: The options used for conversion.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn convert_code(
+    code: &GeckoCode,
+    next_line_number: usize,
+    base_address_changed: bool,
+    repeat_targets: &mut [Option<usize>; 16],
+    options: &ConversionOptions
+) -> Result<String, GeckoCodeConversionError> {
+    match code {
+        GeckoCode::Fill8 { address, count, value } => from_00(*address, *count, *value, base_address_changed),
+
+        GeckoCode::Fill16 { address, count, value } => from_02(*address, *count, *value, base_address_changed),
+
+        GeckoCode::Write32 { address, value } => from_04(*address, *value, base_address_changed),
+
+        GeckoCode::StringWrite { address, bytes } => from_06(*address, bytes, base_address_changed, options),
+
+        GeckoCode::SerialWrite {
+            address,
+            value,
+            value_size,
+            num_additional_writes,
+            address_increment,
+            value_increment
+        } => from_08(*address, *value, *value_size, *num_additional_writes, *address_increment, *value_increment, base_address_changed),
+
+        GeckoCode::If32 { pointer, comparison, endif_first, address, value } => {
+            from_20(*pointer, *comparison, *endif_first, *address, *value, base_address_changed)
+        }
+
+        GeckoCode::If16 { pointer, comparison, endif_first, address, mask, value } => {
+            from_28(*pointer, *comparison, *endif_first, *address, *mask, *value, base_address_changed)
+        }
+
+        GeckoCode::AddressOperation { code_type, flags, register, value } => match code_type & 0x06 {
+            // Load into Base Address, Load into Pointer Offset
+            0x00 => from_40_50(*code_type, *flags, *register, *value),
+
+            // Set Base Address to, Set Pointer Offset to
+            0x02 => from_42_52(*code_type, *flags, *register, *value),
+
+            // Store Base Address at, Store Pointer Offset at
+            0x04 => from_44_54(*code_type, *flags, *register, *value),
+
+            // Put Code Location into Base Address, Put Code Location into Pointer Offset
+            _ => from_46_4e(*code_type, (((*flags & 0xFF) << 0x8) | *register as u16) as i16)
+        },
+
+        GeckoCode::SetRepeat { count, block } => from_60(*count, *block, next_line_number, repeat_targets),
+
+        GeckoCode::ExecuteRepeat { block } => from_62(*block, repeat_targets),
+
+        GeckoCode::Return { condition, block } => from_64_68(0x64, *condition, 0, *block),
+
+        GeckoCode::Goto { condition, offset } => from_64_68(0x66, *condition, *offset, 0),
+
+        GeckoCode::Gosub { condition, offset, block } => from_64_68(0x68, *condition, *offset, *block),
+
+        GeckoCode::SetGeckoRegister { pointer, flags, register, value } => from_80(*pointer, *flags, *register, *value),
+
+        GeckoCode::LoadGeckoRegister { register, address, .. } => from_82(*register, *address),
+
+        GeckoCode::StoreGeckoRegister {
+            pointer,
+            value_size,
+            address_type,
+            num_additional_written_values,
+            register,
+            address
+        } => from_84_94(*pointer, *value_size, *address_type, *num_additional_written_values, *register, *address),
+
+        GeckoCode::RegisterOperation { operation, flags, register, value } => from_86(*operation, *flags, *register, *value),
+
+        GeckoCode::RegisterToRegisterOperation { operation, flags, register, other_register } => {
+            from_88(*operation, *flags, *register, *other_register)
+        }
+
+        GeckoCode::MemoryCopy { offset_source, count, source, destination, offset } => {
+            from_8a_8c(*offset_source, *count, *source, *destination, *offset)
+        }
+
+        GeckoCode::IfCounter { comparison, endif_first, reset, counter, mask, value } => {
+            from_a8(*comparison, *endif_first, *reset, *counter, *mask, *value)
+        }
+
+        GeckoCode::ExecuteAssembly { instructions } => from_c0(instructions, options),
+
+        GeckoCode::InsertAssembly { address, num_lines, instructions } => from_c2(*address, *num_lines, instructions, options),
+
+        GeckoCode::CreateBranch { address, target } => from_c6(*address, *target),
+
+        GeckoCode::FullTerminator { base_address, pointer_offset } => from_e0(*base_address, *pointer_offset),
+
+        GeckoCode::Endif { is_else, count, base_address, pointer_offset } => {
+            from_e2(*is_else, *count, *base_address, *pointer_offset)
+        }
+    }
+}
+
+/// A condition on a value at a known address.
+struct KnownCondition {
+    address: u32,
+    size: u32,
+    mask: u16,
+    comparison: Comparison,
+    value: u32
+}
+
+impl KnownCondition {
+    /// Returns the condition that a conditional code checks, if it checks a known address.
+    fn from_code(code: &GeckoCode) -> Option<KnownCondition> {
+        match code {
+            GeckoCode::If32 { pointer: false, comparison, address, value, .. } => Some(KnownCondition {
+                address: *address,
+                size: 4,
+                mask: 0,
+                comparison: *comparison,
+                value: *value
+            }),
+
+            GeckoCode::If16 { pointer: false, comparison, address, mask, value, .. } => Some(KnownCondition {
+                address: *address,
+                size: 2,
+                mask: *mask,
+                comparison: *comparison,
+                value: *value as u32
+            }),
+
+            _ => None
+        }
+    }
+
+    /// Returns `true` if this condition and `other` can't both be true.
+    fn contradicts(&self, other: &KnownCondition) -> bool {
+        // only conditions on exactly the same value can be compared
+        if (self.address, self.size, self.mask) != (other.address, other.size, other.mask) {
+            return false;
+        }
+
+        let possible = |first: &KnownCondition, second: &KnownCondition| match (first.comparison, second.comparison) {
+            (Comparison::Equal, Comparison::Equal) => Some(first.value == second.value),
+            (Comparison::Equal, Comparison::NotEqual) => Some(first.value != second.value),
+            (Comparison::Equal, Comparison::Greater) => Some(first.value > second.value),
+            (Comparison::Equal, Comparison::Lower) => Some(first.value < second.value),
+            (Comparison::Greater, Comparison::Lower) => Some((first.value as u64) + 1 < second.value as u64),
+            _ => None
+        };
+
+        match possible(self, other).or_else(|| possible(other, self)) {
+            Some(possible) => !possible,
+            None => false
+        }
+    }
+
+    /// Returns `true` if this condition checks a value in `range`.
+    fn overlaps(&self, range: &Range<u32>) -> bool {
+        range.start < self.address.saturating_add(self.size) && self.address < range.end
+    }
+}
+
+/// The most jumps that are followed when modeling the repeats of a gecko code.
+const MAX_MODELED_JUMPS: usize = 0x1000;
+
+/// The line that each block register jumps back to, and the number of repeats it has left.
+type BlockRegisters = [Option<(usize, u16)>; 16];
+
+/// Returns the index of the jump in `codes` that the repeats of a gecko code
+/// cycle through forever, such as a return to the block of a Set Repeat (`0x60`),
+/// which never counts the repeat down. The repeat, goto, gosub and return codes are
+/// followed the way the code handler runs them, skipping the ones that depend on a
+/// conditional, and modeling stops after `MAX_MODELED_JUMPS` jumps.
+fn cyclic_jump(gecko_code: &[u32], codes: &[ConvertedCode]) -> Option<usize> {
+    let mut blocks: BlockRegisters = [None; 16];
+
+    // the blocks each time a jump was taken. if they're the same
+    // as the last time it was taken, it will be taken forever
+    let mut taken: HashSet<(usize, BlockRegisters)> = HashSet::new();
+
+    let mut index = 0;
+
+    while index < codes.len() && taken.len() < MAX_MODELED_JUMPS {
+        let converted = &codes[index];
+
+        let Ok((code, _)) = GeckoCode::parse(&gecko_code[converted.span.clone()]) else {
+            return None;
+        };
+
+        let next_line_number = converted.line_number + converted.span.len() / 2;
+
+        let target = match code {
+            GeckoCode::SetRepeat { count, block } => {
+                blocks[block as usize & 0xF] = Some((next_line_number, count));
+                None
+            }
+
+            GeckoCode::ExecuteRepeat { block } => match blocks[block as usize & 0xF] {
+                Some((target, remaining)) if remaining > 0 => {
+                    blocks[block as usize & 0xF] = Some((target, remaining - 1));
+                    Some(target)
+                }
+
+                _ => None
+            },
+
+            GeckoCode::Return { condition: 0, block } => blocks[block as usize & 0xF].map(|(target, _)| target),
+
+            GeckoCode::Goto { condition: 0, offset } => next_line_number.checked_add_signed(offset as isize),
+
+            GeckoCode::Gosub { condition: 0, offset, block } => {
+                blocks[block as usize & 0xF] = Some((next_line_number, 0));
+                next_line_number.checked_add_signed(offset as isize)
+            }
+
+            _ => None
+        };
+
+        let Some(target) = target else {
+            index += 1;
+            continue;
+        };
+
+        if !taken.insert((index, blocks)) {
+            return Some(index);
+        }
+
+        // a jump into the middle of a code can't be followed
+        index = codes.iter().position(|code| code.line_number == target)?;
+    }
+
+    None
+}
+
+/// Returns the indices of the conditionals in `codes` that can never be true
+/// because of a conditional that they're inside of. Codes between them that
+/// might change the value being checked make the earlier condition unknown.
+fn unreachable_conditionals(gecko_code: &[u32], codes: &[ConvertedCode]) -> Vec<usize> {
+    let mut result: Vec<usize> = Vec::new();
+
+    // the conditions that the current code is inside of
+    let mut active: Vec<Option<KnownCondition>> = Vec::new();
+
+    for (index, converted) in codes.iter().enumerate() {
+        let values = &gecko_code[converted.span.clone()];
+
+        let Ok((code, _)) = GeckoCode::parse(values) else {
+            active.iter_mut().for_each(|condition| *condition = None);
+            continue;
+        };
+
+        match &code {
+            GeckoCode::If32 { endif_first, .. }
+            | GeckoCode::If16 { endif_first, .. }
+            | GeckoCode::IfCounter { endif_first, .. } => {
+                if *endif_first {
+                    active.pop();
+                }
+
+                let condition = KnownCondition::from_code(&code);
+
+                if let Some(condition) = &condition {
+                    if active.iter().flatten().any(|active| active.contradicts(condition)) {
+                        result.push(index);
+                    }
+                }
+
+                active.push(condition);
+            }
+
+            GeckoCode::FullTerminator { .. } => active.clear(),
+
+            // the known conditions are on addresses relative to ba, so they don't apply once it moves
+            GeckoCode::AddressOperation { code_type: 0x40 | 0x42 | 0x46 | 0x50 | 0x52, .. } => {
+                active.iter_mut().for_each(|condition| *condition = None);
+            }
+
+            GeckoCode::Endif { is_else, count, base_address, .. } => {
+                let ended = (*count as usize).max(*is_else as usize);
+                active.truncate(active.len().saturating_sub(ended));
+
+                if *base_address != 0 {
+                    active.iter_mut().for_each(|condition| *condition = None);
+                }
+
+                // the inverted condition isn't known
+                if *is_else {
+                    active.push(None);
+                }
+            }
+
+            // these only change registers
+            GeckoCode::SetGeckoRegister { .. }
+            | GeckoCode::LoadGeckoRegister { .. }
+            | GeckoCode::RegisterOperation { .. }
+            | GeckoCode::RegisterToRegisterOperation { .. } => {}
+
+            GeckoCode::AddressOperation { code_type, .. } if !matches!(code_type & 0x0E, 0x04 | 0x0C) => {}
+
+            _ => {
+                let ranges = write_ranges(values).unwrap_or_default();
+
+                for condition in active.iter_mut() {
+                    let overwritten = ranges.is_empty()
+                        || condition.as_ref().is_some_and(|condition| ranges.iter().any(|range| condition.overlaps(range)));
+
+                    if overwritten {
+                        *condition = None;
+                    }
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Indents each line of `text` by two spaces for each level of `depth`.
+/// Empty lines are left alone.
+fn indent_lines(text: &str, depth: usize) -> String {
+    if depth == 0 {
+        return text.to_string();
+    }
+
+    let indent = "  ".repeat(depth);
+
+    text
+        .split('\n')
+        .map(|line| if line.is_empty() { line.to_string() } else { format!("{indent}{line}") })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Removes the indentation that `indent_lines` added for `depth`.
+fn unindent_lines(text: &str, depth: usize) -> String {
+    let indent = "  ".repeat(depth);
+
+    text
+        .split('\n')
+        .map(|line| line.strip_prefix(&indent).unwrap_or(line))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+/// Returns the lines showing the values that a code was converted from.
+fn source_echo(values: &[u32], little_endian: bool) -> String {
+    let words = |swap: fn(u32) -> u32| values
+        .iter()
+        .map(|value| format!("{:08X}", swap(*value)))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    let mut result = format!("// Source: {}\n", words(|value| value));
+
+    if little_endian {
+        result += &format!("// Source (little-endian): {}\n", words(u32::swap_bytes));
+    }
+
+    result
+}
+
+/// Returns a hash of `values` which stays the same between runs and versions.
+fn content_hash(values: &[u32]) -> u32 {
+    // 32-bit FNV-1a
+    values
+        .iter()
+        .flat_map(|value| value.to_be_bytes())
+        .fold(0x811C9DC5, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193))
+}
+
+/// Checks that the code at `position` strictly follows the documented format.
+/// ## Returns
+/// The position that the code must end at, if it has a declared length.
+fn check_strict(gecko_code: &[u32], position: usize, code_type: u8) -> Result<Option<usize>, GeckoCodeConversionError> {
+    let violation = |reason: String| GeckoCodeConversionError::SpecViolation {
+        line_number: (position / 2) + 1,
+        reason
+    };
+
+    if !matches!(code_type, 0xC0 | 0xC2 | 0xC3) {
+        return Ok(None);
+    }
+
+    // the block must fit in what remains of the gecko code
+    let num_lines = gecko_code[position + 1] as usize;
+    let num_remaining_lines = (gecko_code.len() - position - 2) / 2;
+
+    if num_lines == 0 {
+        return Err(violation("The code declares no lines.".to_string()));
+    }
+
+    if num_lines > num_remaining_lines {
+        return Err(violation(format!("The code declares {num_lines} lines, but only {num_remaining_lines} remain.")));
+    }
+
+    let end = position + 2 + (num_lines * 2);
+    let last_line = (gecko_code[end - 2], gecko_code[end - 1]);
+
+    match code_type {
+        // Execute Assembly must return with a blr
+        0xC0 => {
+            if !matches!(last_line, (0x4E800020, 0) | (_, 0x4E800020)) {
+                return Err(violation("Execute Assembly must end with blr.".to_string()));
+            }
+        }
+
+        // Insert Assembly must end with the terminator
+        _ => {
+            if last_line.1 != 0 {
+                return Err(violation("Insert Assembly must end with 0x00000000.".to_string()));
+            }
+        }
+    }
+
+    Ok(Some(end))
+}
+
+/// The narrowest that wrapped output is allowed to be.
+const MIN_COLUMNS: usize = 16;
+
+/// Wraps the comment lines in `text` that are longer than `max_columns`,
+/// continuing them on the next line with `// `.
+fn wrap_comment_lines(text: &str, max_columns: usize) -> String {
+    let max_columns = max_columns.max(MIN_COLUMNS);
+
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in text.split('\n') {
+        // only comments are wrapped; instructions are left alone
+        if !line.starts_with("//") {
+            lines.push(line.to_string());
+            continue;
+        }
+
+        let mut current = line.to_string();
+
+        while current.chars().count() > max_columns {
+            // the byte position just past the last character that fits
+            let limit = current
+                .char_indices()
+                .nth(max_columns)
+                .map(|(index, _)| index)
+                .unwrap_or(current.len());
+
+            // prefer breaking at a space, but don't break inside the comment marker
+            let break_position = match current[..limit].rfind(' ') {
+                Some(position) if position > 3 => position,
+                _ => limit
+            };
+
+            lines.push(current[..break_position].trim_end().to_string());
+            current = format!("// {}", current[break_position..].trim_start());
+        }
+
+        lines.push(current);
+    }
+
+    lines.join("\n")
+}
+
+/// Returns if a converted code passes the address filter in `options`.
+fn is_in_address_filter(code: &ConvertedCode, options: &ConversionOptions) -> bool {
+    match (&options.only_addresses, code.address) {
+        (None, _) => true,
+        (Some(range), Some(address)) => range.contains(&address),
+        (Some(_), None) => false
+    }
+}
+
+/// Returns a table of contents listing each of the converted codes.
+fn table_of_contents<'a>(codes: impl Iterator<Item = &'a ConvertedCode>) -> String {
+    let mut result = "// - Table of Contents -\n".to_string();
+
+    for code in codes {
+        let name = code_type_name(code.code_type).unwrap_or("Unknown");
+
+        result += &match code.address {
+            Some(address) => format!("// Line {}: {name} (0x{:08X})\n", code.line_number, address),
+            None => format!("// Line {}: {name}\n", code.line_number)
+        };
+    }
+
+    result
+}
+
+pub fn convert_from_gecko_code_values(gecko_code: &[u32]) -> Result<String, GeckoCodeConversionError> {
+    convert_with_options(gecko_code, &ConversionOptions::default())
+}
+
+/// Converts the gecko code in `input`, as it would be pasted from a cheat file.
+pub fn convert_from_gecko_text(input: &str) -> Result<String, GeckoCodeConversionError> {
+    convert_from_gecko_code_values(&parse_gecko_text(input)?)
+}
+
+/// Converts only the code at `index` (starting from 0) in a gecko code list.
+pub fn convert_nth(gecko_code: &[u32], index: usize) -> Result<String, GeckoCodeConversionError> {
+    let mut codes = convert_codes(gecko_code)?;
+    let count = codes.len();
+
+    if index >= count {
+        return Err(GeckoCodeConversionError::IndexOutOfRange { index, count });
+    }
+
+    Ok(codes.swap_remove(index).text)
+}
+
+/// Converts a gecko code using the given `options`.
+pub fn convert_with_options(gecko_code: &[u32], options: &ConversionOptions) -> Result<String, GeckoCodeConversionError> {
+    let codes = convert_codes_with_options(gecko_code, options)?;
+
+    let mut result = String::new();
+
+    if options.table_of_contents {
+        let contents = table_of_contents(codes.iter().filter(|code| is_in_address_filter(code, options)));
+
+        result += &match options.max_columns {
+            Some(max_columns) => wrap_comment_lines(&contents, max_columns),
+            None => contents
+        };
+
+        result += "\n// ---\n\n";
+    }
+
+    for code in &codes {
+        if is_in_address_filter(code, options) {
+            result += &code.text;
+        } else {
+            result += "// [filtered]";
+        }
+
+        result += "\n\n// ---\n\n";
+    }
+
+    Ok(result)
+}
+
+/// Converts a gecko code, grouping the converted codes by their category.
+/// Each converted code starts with the line number it was on, since the
+/// order of the codes is only kept within each category.
+pub fn convert_grouped(gecko_code: &[u32]) -> Result<BTreeMap<CodeCategory, Vec<String>>, GeckoCodeConversionError> {
+    let mut result: BTreeMap<CodeCategory, Vec<String>> = BTreeMap::new();
+
+    for code in convert_codes(gecko_code)? {
+        result
+            .entry(code_category(code.code_type))
+            .or_default()
+            .push(format!("// Line {}\n{}", code.line_number, unindent_lines(&code.text, code.depth)));
+    }
+
+    Ok(result)
+}
+
+/// Returns if a line of output is only a decorative separator, such as `// ---`.
+fn is_separator_line(line: &str) -> bool {
+    line.strip_prefix("//")
+        .is_some_and(|rest| rest.chars().all(|c| matches!(c, '-' | '=' | ' ')))
+}
+
+/// Normalizes converted output so that outputs that only differ in
+/// separators, whitespace, or the case of hex values compare equal.
+pub fn canonicalize_output(output: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || is_separator_line(line) {
+            continue;
+        }
+
+        let tokens = line
+            .split_whitespace()
+            .map(|token| {
+                if token.contains("0x") || token.contains("0X") {
+                    token.to_lowercase()
+                } else {
+                    token.to_string()
+                }
+            })
+            .collect::<Vec<String>>();
+
+        lines.push(tokens.join(" "));
+    }
+
+    lines.join("\n")
+}
+
+/// Returns `line` without its comment, which starts with `//` or `#`.
+pub fn strip_comment(line: &str) -> &str {
+    match line.find("//").into_iter().chain(line.find('#')).min() {
+        Some(start) => &line[..start],
+        None => line
+    }
+}
+
+/// Parses gecko code text into values. Comments are skipped.
+pub fn parse_gecko_text(input: &str) -> Result<Vec<u32>, GeckoCodeConversionError> {
+    parse_gecko_text_groups(input).map(|(values, _)| values)
+}
+
+/// Parses gecko code text into values. Comments are skipped.
+/// ## Returns
+/// The values, along with the index of the first value of
+/// each group of lines separated by blank lines.
+pub fn parse_gecko_text_groups(input: &str) -> Result<(Vec<u32>, Vec<usize>), GeckoCodeConversionError> {
+    let mut values: Vec<u32> = Vec::new();
+    let mut group_starts: Vec<usize> = Vec::new();
+
+    let mut in_group = false;
+
+    for line in input.lines() {
+        let mut words = strip_comment(line).split([' ', '\r']).collect::<Vec<&str>>();
+        words.retain(|w| !w.is_empty());
+
+        // a blank line ends the current group, but a comment doesn't
+        if words.is_empty() {
+            if line.trim().is_empty() {
+                in_group = false;
+            }
+
+            continue;
+        }
+
+        if !in_group {
+            group_starts.push(values.len());
+            in_group = true;
+        }
+
+        for word in words {
+            let Ok(value) = u32::from_str_radix(word, 16) else {
+                let err = GeckoCodeConversionError::ParseError {
+                    reason: format!("Invalid hex value: \"{word}\"")
+                };
+
+                return Err(err);
+            };
+
+            values.push(value);
+        }
+    }
+
+    Ok((values, group_starts))
+}
+
+/// Returns the instructions in an Execute Assembly (`0xC0`) or Insert
+/// Assembly (`0xC2`) code, as their raw value, their disassembly, and
+/// their offset within the block. `gecko_code` must start with the code.
+pub fn block_instructions(gecko_code: &[u32]) -> Result<Vec<(u32, String, u32)>, GeckoCodeConversionError> {
+    if gecko_code.len() < 2 {
+        return Err(GeckoCodeConversionError::Malformed);
+    }
+
+    let byte = ((gecko_code[0] & 0xFF000000) >> 0x18) as u8;
+
+    if !matches!(byte, 0xC0 | 0xC2 | 0xC3) {
+        let err = GeckoCodeConversionError::InvalidType {
+            line_number: 1,
+            value: gecko_code[0]
+        };
+
+        return Err(err);
+    }
+
+    // the second value is the number of lines of assembly
+    let num_values = gecko_code[1] as usize * 2;
+
+    let Some(values) = gecko_code[2..].get(..num_values) else {
+        return Err(GeckoCodeConversionError::Malformed);
+    };
+
+    // the padding is trimmed the same as when converting
+    let values = match byte {
+        0xC0 => execute_assembly_instructions(values),
+        _ => insert_assembly_instructions(values)
+    };
+
+    let instructions = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (*value, ppc::code_to_instruction(*value), index as u32 * 4))
+        .collect();
+
+    Ok(instructions)
+}
+
+/* GCT */
+
+/// The size of the GCT header and terminator, in bytes.
+const GCT_HEADER_AND_TERMINATOR_SIZE: usize = 16;
+
+/// The value that a GCT file's header is made of.
+const GCT_HEADER_VALUE: u32 = 0x00D0C0DE;
+
+/// The two values that a GCT file ends with.
+const GCT_TERMINATOR: [u32; 2] = [0xF0000000, 0x00000000];
+
+/// Parses the contents of a GCT file into the values of its gecko code,
+/// without the header and terminator.
+pub fn parse_gct(gct: &[u8]) -> Result<Vec<u32>, GeckoCodeConversionError> {
+    if !gct.len().is_multiple_of(4) {
+        return Err(GeckoCodeConversionError::Malformed);
+    }
+
+    let values = gct
+        .chunks_exact(4)
+        .map(|chunk| u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect::<Vec<u32>>();
+
+    let Some(values) = values.strip_prefix(&[GCT_HEADER_VALUE; 2]) else {
+        let err = GeckoCodeConversionError::ParseError {
+            reason: "Missing GCT header.".to_string()
+        };
+
+        return Err(err);
+    };
+
+    let Some(values) = values.strip_suffix(&GCT_TERMINATOR) else {
+        let err = GeckoCodeConversionError::ParseError {
+            reason: "Missing GCT terminator.".to_string()
+        };
+
+        return Err(err);
+    };
+
+    Ok(values.to_vec())
+}
+
+/// Converts the contents of a GCT file.
+/// A GCT file without any codes is valid, and is converted to `// (no codes)`.
+pub fn convert_from_gct(gct: &[u8]) -> Result<String, GeckoCodeConversionError> {
+    let gecko_code = parse_gct(gct)?;
+
+    if gecko_code.is_empty() {
+        return Ok("// (no codes)".to_string());
+    }
+
+    convert_from_gecko_code_values(&gecko_code)
+}
+
+/// Returns the size, in bytes, of a GCT file containing `gecko_code`.
+pub fn gct_size(gecko_code: &[u32]) -> usize {
+    (gecko_code.len() * 4) + GCT_HEADER_AND_TERMINATOR_SIZE
+}
+
+/// Returns the size, in bytes, of a GCT file containing the gecko code in `input`.
+pub fn gct_size_from_text(input: &str) -> Result<usize, GeckoCodeConversionError> {
+    Ok(gct_size(&parse_gecko_text(input)?))
+}
+
+
+/* Structured Codes */
+
+/// How a conditional code compares the value in memory to its operand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Greater,
+    Lower
+}
+
+impl Comparison {
+    /// Returns the comparison used by a conditional code type.
+    fn from_code_type(code_type: u8) -> Comparison {
+        match code_type & 0x06 {
+            0x00 => Comparison::Equal,
+            0x02 => Comparison::NotEqual,
+            0x04 => Comparison::Greater,
+            _ => Comparison::Lower
+        }
+    }
+
+    /// Returns the bits of the code type that hold the comparison.
+    fn code_type_bits(self) -> u8 {
+        match self {
+            Comparison::Equal => 0x00,
+            Comparison::NotEqual => 0x02,
+            Comparison::Greater => 0x04,
+            Comparison::Lower => 0x06
+        }
+    }
+
+    /// Returns the name of the comparison, such as `Not Equal`.
+    fn name(self) -> &'static str {
+        match self {
+            Comparison::Equal => "Equal",
+            Comparison::NotEqual => "Not Equal",
+            Comparison::Greater => "Greater Than",
+            Comparison::Lower => "Lower Than"
+        }
+    }
+
+    /// Returns the operator for the comparison, such as `==`.
+    pub fn operator(self) -> &'static str {
+        match self {
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+            Comparison::Greater => ">",
+            Comparison::Lower => "<"
+        }
+    }
+}
+
+/// A gecko code, decoded into its individual fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeckoCode {
+    /// # 0x00: 8-bit RAM Write & Fill
+    Fill8 {
+        address: u32,
+        count: u16,
+        value: u8
+    },
+
+    /// # 0x02: 16-bit RAM Write & Fill
+    Fill16 {
+        address: u32,
+        count: u16,
+        value: u16
+    },
+
+    /// # 0x04: 32-bit RAM Write
+    Write32 {
+        address: u32,
+        value: u32
+    },
+
+    /// # 0x06: String RAM Write
+    StringWrite {
+        address: u32,
+        bytes: Vec<u8>
+    },
+
+    /// # 0x08: Serial RAM Write
+    /// `value_size` is 0 for 8-bit, 1 for 16-bit and 2 for 32-bit values.
+    SerialWrite {
+        address: u32,
+        value: u32,
+        value_size: u8,
+        num_additional_writes: u16,
+        address_increment: u16,
+        value_increment: u32
+    },
+
+    /// # 0x20 - 0x27, 0x30 - 0x37: 32-bit If
+    /// `pointer` indicates if this is the `po`-relative (`0x3X`) form,
+    /// in which case `address` is the offset from `po`.
+    If32 {
+        pointer: bool,
+        comparison: Comparison,
+        endif_first: bool,
+        address: u32,
+        value: u32
+    },
+
+    /// # 0x28 - 0x2F, 0x38 - 0x3F: 16-bit If
+    /// The bits set in `mask` are ignored when comparing.
+    /// `pointer` indicates if this is the `po`-relative (`0x3X`) form,
+    /// in which case `address` is the offset from `po`.
+    If16 {
+        pointer: bool,
+        comparison: Comparison,
+        endif_first: bool,
+        address: u32,
+        mask: u16,
+        value: u16
+    },
+
+    /// # 0x40 - 0x4E, 0x50 - 0x5C: Base Address and Pointer Offset Codes
+    /// `flags` holds the bits between the code type and the register.
+    AddressOperation {
+        code_type: u8,
+        flags: u16,
+        register: u8,
+        value: u32
+    },
+
+    /// # 0x60: Set Repeat
+    /// `block` is the block register (`b0` - `b15`) that holds the repeat.
+    SetRepeat {
+        count: u16,
+        block: u8
+    },
+
+    /// # 0x62: Execute Repeat
+    ExecuteRepeat {
+        block: u8
+    },
+
+    /// # 0x64: Return
+    /// `condition` is 0 to always return, 1 to only return if the
+    /// current conditional is true, or 2 to only return if it's false.
+    Return {
+        condition: u8,
+        block: u8
+    },
+
+    /// # 0x66: Goto
+    /// `offset` is the number of lines to jump by.
+    Goto {
+        condition: u8,
+        offset: i16
+    },
+
+    /// # 0x68: Gosub
+    /// The location of the next line is stored in `block` before jumping.
+    Gosub {
+        condition: u8,
+        offset: i16,
+        block: u8
+    },
+
+    /// # 0x80, 0x90: Set Gecko Register to
+    /// `flags` holds the bits between the code type and the register.
+    /// `pointer` indicates if this is the `po`-relative (`0x90`) form.
+    SetGeckoRegister {
+        pointer: bool,
+        flags: u16,
+        register: u8,
+        value: u32
+    },
+
+    /// # 0x82: Load into Gecko Register
+    /// `flags` holds the bits between the code type and the register.
+    LoadGeckoRegister {
+        flags: u16,
+        register: u8,
+        address: u32
+    },
+
+    /// # 0x84, 0x94: Store Gecko Register at
+    /// `pointer` indicates if this is the `po`-relative (`0x94`) form.
+    StoreGeckoRegister {
+        pointer: bool,
+        value_size: u8,
+        address_type: u8,
+        num_additional_written_values: u16,
+        register: u8,
+        address: u32
+    },
+
+    /// # 0x86: Gecko Register Operation
+    /// `flags` indicates which of the operands are used as addresses.
+    RegisterOperation {
+        operation: u8,
+        flags: u8,
+        register: u8,
+        value: u32
+    },
+
+    /// # 0x88: Gecko Register Operation
+    /// The operation is between `register` and `other_register`.
+    /// `flags` indicates which of the operands are used as addresses.
+    RegisterToRegisterOperation {
+        operation: u8,
+        flags: u8,
+        register: u8,
+        other_register: u8
+    },
+
+    /// # 0x8A, 0x8C: Memory Copy
+    /// `count` bytes are copied from the address in `source` to the address in `destination`.
+    /// `offset` is added to the destination for `0x8A`, and to the source for `0x8C`.
+    /// A register of `0xF` is the base address instead of a gecko register.
+    MemoryCopy {
+        offset_source: bool,
+        count: u16,
+        source: u8,
+        destination: u8,
+        offset: u32
+    },
+
+    /// # 0xA8 - 0xAE: 16-bit If Counter
+    /// `counter` is the code's own counter, which is stored in the code and
+    /// changed by the code handler. The bits set in `mask` are ignored when comparing.
+    /// `reset` indicates if the counter is reset when the condition is true.
+    IfCounter {
+        comparison: Comparison,
+        endif_first: bool,
+        reset: bool,
+        counter: u16,
+        mask: u16,
+        value: u16
+    },
+
+    /// # 0xC0: Execute Assembly
+    ExecuteAssembly {
+        instructions: Vec<u32>
+    },
+
+    /// # 0xC2: Insert Assembly
+    /// `num_lines` is the declared number of lines, which isn't always right.
+    /// `instructions` holds every value after the first line, including the terminator.
+    InsertAssembly {
+        address: u32,
+        num_lines: u32,
+        instructions: Vec<u32>
+    },
+
+    /// # 0xC6: Create a Branch
+    CreateBranch {
+        address: u32,
+        target: u32
+    },
+
+    /// # 0xE0: Full Terminator
+    /// `base_address` and `pointer_offset` are the upper 16 bits that
+    /// `ba` and `po` are set to. Each is left alone if it's 0.
+    FullTerminator {
+        base_address: u16,
+        pointer_offset: u16
+    },
+
+    /// # 0xE2: Endif
+    /// Ends `count` conditionals, and then starts an else if `is_else` is set.
+    /// `base_address` and `pointer_offset` work the same as for the full terminator.
+    Endif {
+        is_else: bool,
+        count: u8,
+        base_address: u16,
+        pointer_offset: u16
+    }
+}
+
+/// Returns the offset of `address` from the default base address,
+/// if it's one that a code can reach (see `code_address`).
+fn encode_offset(address: u32) -> Result<u32, GeckoCodeConversionError> {
+    if (0x80000000..0x82000000).contains(&address) {
+        Ok(address - 0x80000000)
+    } else {
+        Err(GeckoCodeConversionError::UnreachableAddress { address })
+    }
+}
+
+/// Returns the first value of a code that targets `address`.
+fn encode_code_address(code_type: u8, address: u32) -> Result<u32, GeckoCodeConversionError> {
+    let offset = encode_offset(address)?;
+
+    let code_type = if offset & 0x01000000 != 0 { code_type | 1 } else { code_type };
+
+    Ok(((code_type as u32) << 0x18) | (offset & 0x00FFFFFF))
+}
+
+impl GeckoCode {
+    /// Parses the code at the start of `gecko_code`.
+    /// ## Returns
+    /// The code, along with the number of values it is made of.
+    pub fn parse(gecko_code: &[u32]) -> Result<(GeckoCode, usize), GeckoCodeConversionError> {
+        if gecko_code.len() < 2 {
+            return Err(GeckoCodeConversionError::Malformed);
+        }
+
+        let first = gecko_code[0];
+        let second = gecko_code[1];
+
+        let byte = ((first & 0xFF000000) >> 0x18) as u8;
+        let larger_address = !byte.is_multiple_of(2);
+
+        // returns the values following the first line, or an error if they aren't all there.
+        // a header on its own is malformed, and one whose body is cut off is truncated
+        let body = |num_values: usize| match gecko_code[2..].get(..num_values) {
+            Some(values) => Ok(values),
+            None if gecko_code.len() == 2 => Err(GeckoCodeConversionError::Malformed),
+            None => Err(GeckoCodeConversionError::Truncated { line_number: 1, opcode: byte })
+        };
+
+        let code = match byte {
+            0x00 | 0x01 => GeckoCode::Fill8 {
+                address: code_address(first, larger_address),
+                count: (second >> 0x10) as u16,
+                value: (second & 0x000000FF) as u8
+            },
+
+            0x02 | 0x03 => GeckoCode::Fill16 {
+                address: code_address(first, larger_address),
+                count: (second >> 0x10) as u16,
+                value: (second & 0x0000FFFF) as u16
+            },
+
+            0x04 | 0x05 => GeckoCode::Write32 {
+                address: code_address(first, larger_address),
+                value: second
+            },
+
+            0x06 | 0x07 => {
+                let num_bytes = second as usize;
+
+                let bytes = body(num_bytes.div_ceil(8) * 2)?
+                    .iter()
+                    .flat_map(|value| value.to_be_bytes())
+                    .take(num_bytes)
+                    .collect();
+
+                GeckoCode::StringWrite {
+                    address: code_address(first, larger_address),
+                    bytes
+                }
+            }
+
+            0x08 | 0x09 => {
+                let body = body(2)?;
+
+                GeckoCode::SerialWrite {
+                    address: code_address(first, larger_address),
+                    value: second,
+                    value_size: (body[0] >> 0x1C) as u8,
+                    num_additional_writes: ((body[0] >> 0x10) & 0xFFF) as u16,
+                    address_increment: (body[0] & 0xFFFF) as u16,
+                    value_increment: body[1]
+                }
+            }
+
+            0x20..=0x27 | 0x30..=0x37 => GeckoCode::If32 {
+                pointer: byte & 0x10 != 0,
+                comparison: Comparison::from_code_type(byte),
+                endif_first: first & 1 != 0,
+                address: conditional_address(first, byte & 0x10 != 0),
+                value: second
+            },
+
+            0x28..=0x2F | 0x38..=0x3F => GeckoCode::If16 {
+                pointer: byte & 0x10 != 0,
+                comparison: Comparison::from_code_type(byte),
+                endif_first: first & 1 != 0,
+                address: conditional_address(first, byte & 0x10 != 0),
+                mask: (second >> 0x10) as u16,
+                value: (second & 0xFFFF) as u16
+            },
+
+            0x40..=0x4E | 0x50..=0x5C if byte.is_multiple_of(2) && byte != 0x56 => GeckoCode::AddressOperation {
+                code_type: byte,
+                flags: ((first >> 0x8) & 0xFFFF) as u16,
+                register: (first & 0xFF) as u8,
+                value: second
+            },
+
+            0x60 => GeckoCode::SetRepeat {
+                count: (first & 0xFFFF) as u16,
+                block: (second & 0xF) as u8
+            },
+
+            0x62 => GeckoCode::ExecuteRepeat {
+                block: (second & 0xF) as u8
+            },
+
+            0x64 => GeckoCode::Return {
+                condition: ((first >> 0x14) & 0xF) as u8,
+                block: (second & 0xF) as u8
+            },
+
+            0x66 => GeckoCode::Goto {
+                condition: ((first >> 0x14) & 0xF) as u8,
+                offset: (first & 0xFFFF) as u16 as i16
+            },
+
+            0x68 => GeckoCode::Gosub {
+                condition: ((first >> 0x14) & 0xF) as u8,
+                offset: (first & 0xFFFF) as u16 as i16,
+                block: (second & 0xF) as u8
+            },
+
+            0x80 | 0x90 => GeckoCode::SetGeckoRegister {
+                pointer: byte == 0x90,
+                flags: ((first >> 0x8) & 0xFFFF) as u16,
+                register: (first & 0xFF) as u8,
+                value: second
+            },
+
+            0x82 => GeckoCode::LoadGeckoRegister {
+                flags: ((first >> 0x8) & 0xFFFF) as u16,
+                register: (first & 0xFF) as u8,
+                address: second
+            },
+
+            0x84 | 0x94 => GeckoCode::StoreGeckoRegister {
+                pointer: byte == 0x94,
+                value_size: ((first & 0x00F00000) >> 0x14) as u8,
+                address_type: ((first & 0x000F0000) >> 0x10) as u8,
+                num_additional_written_values: ((first & 0x0000FFF0) >> 0x4) as u16,
+                register: (first & 0xF) as u8,
+                address: second
+            },
+
+            0x86 => GeckoCode::RegisterOperation {
+                operation: ((first >> 0x14) & 0xF) as u8,
+                flags: ((first >> 0x10) & 0xF) as u8,
+                register: (first & 0xF) as u8,
+                value: second
+            },
+
+            0x88 => GeckoCode::RegisterToRegisterOperation {
+                operation: ((first >> 0x14) & 0xF) as u8,
+                flags: ((first >> 0x10) & 0xF) as u8,
+                register: (first & 0xF) as u8,
+                other_register: (second & 0xF) as u8
+            },
+
+            0x8A | 0x8C => GeckoCode::MemoryCopy {
+                offset_source: byte == 0x8C,
+                count: ((first >> 0x8) & 0xFFFF) as u16,
+                source: ((first >> 0x4) & 0xF) as u8,
+                destination: (first & 0xF) as u8,
+                offset: second
+            },
+
+            0xA8 | 0xAA | 0xAC | 0xAE => GeckoCode::IfCounter {
+                comparison: Comparison::from_code_type(byte),
+                endif_first: first & 0x10 != 0,
+                reset: first & 0x80 != 0,
+                counter: ((first >> 0x8) & 0xFFFF) as u16,
+                mask: (second >> 0x10) as u16,
+                value: (second & 0xFFFF) as u16
+            },
+
+            0xC0 => GeckoCode::ExecuteAssembly {
+                instructions: body(second as usize * 2)?.to_vec()
+            },
+
+            0xC2 | 0xC3 => GeckoCode::InsertAssembly {
+                address: code_address(first, larger_address),
+                num_lines: second,
+                instructions: insert_assembly_body(gecko_code)?.to_vec()
+            },
+
+            0xC6 | 0xC7 => GeckoCode::CreateBranch {
+                address: code_address(first, larger_address),
+                target: second
+            },
+
+            0xE0 => GeckoCode::FullTerminator {
+                base_address: (second >> 0x10) as u16,
+                pointer_offset: (second & 0xFFFF) as u16
+            },
+
+            0xE2 => GeckoCode::Endif {
+                is_else: (first >> 0x14) & 0xF != 0,
+                count: (first & 0xFF) as u8,
+                base_address: (second >> 0x10) as u16,
+                pointer_offset: (second & 0xFFFF) as u16
+            },
+
+            _ => {
+                let err = GeckoCodeConversionError::InvalidType {
+                    line_number: 1,
+                    value: first
+                };
+
+                return Err(err);
+            }
+        };
+
+        let num_values = code.encode()?.len();
+
+        Ok((code, num_values))
+    }
+
+    /// Returns the address that the code targets, if it has one.
+    pub fn address(&self) -> Option<u32> {
+        match self {
+            GeckoCode::Fill8 { address, .. }
+            | GeckoCode::Fill16 { address, .. }
+            | GeckoCode::Write32 { address, .. }
+            | GeckoCode::StringWrite { address, .. }
+            | GeckoCode::SerialWrite { address, .. }
+            | GeckoCode::InsertAssembly { address, .. }
+            | GeckoCode::CreateBranch { address, .. } => Some(*address),
+
+            // only the first address type is absolute
+            GeckoCode::LoadGeckoRegister { address, flags, .. } if flags & 0x0F00 == 0 => Some(*address),
+            GeckoCode::StoreGeckoRegister { address, address_type: 0, .. } => Some(*address),
+
+            GeckoCode::If32 { address, pointer: false, .. }
+            | GeckoCode::If16 { address, pointer: false, .. } => Some(*address),
+
+            _ => None
+        }
+    }
+
+    fn address_mut(&mut self) -> Option<&mut u32> {
+        match self {
+            GeckoCode::Fill8 { address, .. }
+            | GeckoCode::Fill16 { address, .. }
+            | GeckoCode::Write32 { address, .. }
+            | GeckoCode::StringWrite { address, .. }
+            | GeckoCode::SerialWrite { address, .. }
+            | GeckoCode::InsertAssembly { address, .. }
+            | GeckoCode::CreateBranch { address, .. } => Some(address),
+
+            // only the first address type is absolute
+            GeckoCode::LoadGeckoRegister { address, flags, .. } if *flags & 0x0F00 == 0 => Some(address),
+            GeckoCode::StoreGeckoRegister { address, address_type: 0, .. } => Some(address),
+
+            GeckoCode::If32 { address, pointer: false, .. }
+            | GeckoCode::If16 { address, pointer: false, .. } => Some(address),
+
+            _ => None
+        }
+    }
+
+    /// Encodes the code back into its values.
+    /// ## Returns
+    /// The values, or an error if the code targets an address that can't be encoded.
+    pub fn encode(&self) -> Result<Vec<u32>, GeckoCodeConversionError> {
+        let values = match self {
+            GeckoCode::Fill8 { address, count, value } => vec![
+                encode_code_address(0x00, *address)?,
+                ((*count as u32) << 0x10) | *value as u32
+            ],
+
+            GeckoCode::Fill16 { address, count, value } => vec![
+                encode_code_address(0x02, *address)?,
+                ((*count as u32) << 0x10) | *value as u32
+            ],
+
+            GeckoCode::Write32 { address, value } => vec![
+                encode_code_address(0x04, *address)?,
+                *value
+            ],
+
+            GeckoCode::StringWrite { address, bytes } => {
+                let mut values = vec![
+                    encode_code_address(0x06, *address)?,
+                    bytes.len() as u32
+                ];
+
+                // pad the contents to a full line
+                let mut padded_bytes = bytes.clone();
+                padded_bytes.resize(bytes.len().div_ceil(8) * 8, 0);
+
+                values.extend(
+                    padded_bytes
+                        .chunks_exact(4)
+                        .map(|chunk| u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                );
+
+                values
+            }
+
+            GeckoCode::SerialWrite {
+                address,
+                value,
+                value_size,
+                num_additional_writes,
+                address_increment,
+                value_increment
+            } => vec![
+                encode_code_address(0x08, *address)?,
+                *value,
+                ((*value_size as u32 & 0xF) << 0x1C)
+                    | ((*num_additional_writes as u32 & 0xFFF) << 0x10)
+                    | *address_increment as u32,
+                *value_increment
+            ],
+
+            GeckoCode::If32 { pointer, comparison, endif_first, address, value } => {
+                let code_type = if *pointer { 0x30 } else { 0x20 } | comparison.code_type_bits();
+
+                // the base address isn't part of the code
+                let offset = if *pointer { *address } else { encode_offset(*address)? };
+
+                vec![
+                    ((code_type as u32) << 0x18) | (offset & 0x01FFFFFE) | *endif_first as u32,
+                    *value
+                ]
+            }
+
+            GeckoCode::If16 { pointer, comparison, endif_first, address, mask, value } => {
+                let code_type = if *pointer { 0x38 } else { 0x28 } | comparison.code_type_bits();
+
+                // the base address isn't part of the code
+                let offset = if *pointer { *address } else { encode_offset(*address)? };
+
+                vec![
+                    ((code_type as u32) << 0x18) | (offset & 0x01FFFFFE) | *endif_first as u32,
+                    ((*mask as u32) << 0x10) | *value as u32
+                ]
+            }
+
+            GeckoCode::AddressOperation { code_type, flags, register, value } => vec![
+                ((*code_type as u32) << 0x18) | ((*flags as u32) << 0x8) | *register as u32,
+                *value
+            ],
+
+            GeckoCode::SetRepeat { count, block } => vec![0x60000000 | *count as u32, *block as u32 & 0xF],
+
+            GeckoCode::ExecuteRepeat { block } => vec![0x62000000, *block as u32 & 0xF],
+
+            GeckoCode::Return { condition, block } => vec![
+                0x64000000 | ((*condition as u32 & 0xF) << 0x14),
+                *block as u32 & 0xF
+            ],
+
+            GeckoCode::Goto { condition, offset } => vec![
+                0x66000000 | ((*condition as u32 & 0xF) << 0x14) | *offset as u16 as u32,
+                0
+            ],
+
+            GeckoCode::Gosub { condition, offset, block } => vec![
+                0x68000000 | ((*condition as u32 & 0xF) << 0x14) | *offset as u16 as u32,
+                *block as u32 & 0xF
+            ],
+
+            GeckoCode::SetGeckoRegister { pointer, flags, register, value } => {
+                let code_type: u32 = if *pointer { 0x90 } else { 0x80 };
+
+                vec![
+                    (code_type << 0x18) | ((*flags as u32) << 0x8) | *register as u32,
+                    *value
+                ]
+            }
+
+            GeckoCode::LoadGeckoRegister { flags, register, address } => vec![
+                0x82000000 | ((*flags as u32) << 0x8) | *register as u32,
+                *address
+            ],
+
+            GeckoCode::StoreGeckoRegister {
+                pointer,
+                value_size,
+                address_type,
+                num_additional_written_values,
+                register,
+                address
+            } => {
+                let code_type: u32 = if *pointer { 0x94 } else { 0x84 };
+
+                vec![
+                    (code_type << 0x18)
+                        | ((*value_size as u32 & 0xF) << 0x14)
+                        | ((*address_type as u32 & 0xF) << 0x10)
+                        | ((*num_additional_written_values as u32 & 0xFFF) << 0x4)
+                        | (*register as u32 & 0xF),
+                    *address
+                ]
+            }
+
+            GeckoCode::RegisterOperation { operation, flags, register, value } => vec![
+                0x86000000
+                    | ((*operation as u32 & 0xF) << 0x14)
+                    | ((*flags as u32 & 0xF) << 0x10)
+                    | (*register as u32 & 0xF),
+                *value
+            ],
+
+            GeckoCode::RegisterToRegisterOperation { operation, flags, register, other_register } => vec![
+                0x88000000
+                    | ((*operation as u32 & 0xF) << 0x14)
+                    | ((*flags as u32 & 0xF) << 0x10)
+                    | (*register as u32 & 0xF),
+                *other_register as u32 & 0xF
+            ],
+
+            GeckoCode::MemoryCopy { offset_source, count, source, destination, offset } => {
+                let code_type: u32 = if *offset_source { 0x8C } else { 0x8A };
+
+                vec![
+                    (code_type << 0x18)
+                        | ((*count as u32) << 0x8)
+                        | ((*source as u32 & 0xF) << 0x4)
+                        | (*destination as u32 & 0xF),
+                    *offset
+                ]
+            }
+
+            GeckoCode::IfCounter { comparison, endif_first, reset, counter, mask, value } => {
+                let code_type = 0xA8 | comparison.code_type_bits();
+
+                vec![
+                    ((code_type as u32) << 0x18)
+                        | ((*counter as u32) << 0x8)
+                        | if *reset { 0x80 } else { 0 }
+                        | if *endif_first { 0x10 } else { 0 },
+                    ((*mask as u32) << 0x10) | *value as u32
+                ]
+            }
+
+            GeckoCode::ExecuteAssembly { instructions } => {
+                let mut values = vec![0xC0000000, instructions.len().div_ceil(2) as u32];
+                values.extend(instructions);
+                values
+            }
+
+            GeckoCode::InsertAssembly { address, num_lines, instructions } => {
+                let mut values = vec![
+                    encode_code_address(0xC2, *address)?,
+                    *num_lines
+                ];
+
+                values.extend(instructions);
+                values
+            }
+
+            GeckoCode::CreateBranch { address, target } => vec![
+                encode_code_address(0xC6, *address)?,
+                *target
+            ],
+
+            GeckoCode::FullTerminator { base_address, pointer_offset } => vec![
+                0xE0000000,
+                ((*base_address as u32) << 0x10) | *pointer_offset as u32
+            ],
+
+            GeckoCode::Endif { is_else, count, base_address, pointer_offset } => vec![
+                0xE2000000 | if *is_else { 0x00100000 } else { 0 } | *count as u32,
+                ((*base_address as u32) << 0x10) | *pointer_offset as u32
+            ]
+        };
+
+        Ok(values)
+    }
+}
+
+/// Returns the values following the first line of the Insert Assembly (`0xC2`)
+/// code at the start of `gecko_code`, up to and including its terminator.
+/// When the number of lines can't be right, the end of the code is guessed from its contents.
+fn insert_assembly_body(gecko_code: &[u32]) -> Result<&[u32], GeckoCodeConversionError> {
+    let byte = (gecko_code[0] >> 0x18) as u8;
+    let num_lines = gecko_code[1] as usize;
+    let values = &gecko_code[2..];
+
+    // the number of lines isn't always right, but there must be some instructions
+    if num_lines > 0 && values.is_empty() {
+        return Err(GeckoCodeConversionError::Malformed);
+    }
+
+    // the declared lines can be trusted if they're there and end with the terminator
+    if num_lines > 0 && values.get(num_lines * 2 - 1) == Some(&0) {
+        return Ok(&values[..num_lines * 2]);
+    }
+
+    let mut end = 0;
+
+    while end < values.len() {
+        let left_code = values[end];
+
+        let Some(&right_code) = values.get(end + 1) else {
+            return Err(GeckoCodeConversionError::Truncated {
+                line_number: ((end + 3) / 2) + 1,
+                opcode: byte
+            });
+        };
+
+        end += 2;
+
+        // gecko codes are written by all sorts of people
+        // and as a result don't always follow the "rules"
+        // set in place by the documentation
+
+        // by that standard, many C2 codes are "malformed", but many
+        // of these codes work regardless. sometimes these codes include
+        // invalid instructions, but they'll never be hit due to
+        // some branch being placed before they can be executed
+
+        // so, there are differing conditions in which a C2 code would end,
+        // and all of them need to be checked
+
+        // check if this is the end of the code
+        if (left_code == 0x60000000 && right_code == 0) || right_code == 0x60000000 {
+            break;
+        }
+    }
+
+    Ok(&values[..end])
+}
+
+/// Adds `num_lines` to the line number of an error from parsing a code
+/// that starts `num_lines` lines into the whole gecko code.
+fn offset_line_number(err: GeckoCodeConversionError, num_lines: usize) -> GeckoCodeConversionError {
+    match err {
+        GeckoCodeConversionError::InvalidType { line_number, value } => GeckoCodeConversionError::InvalidType {
+            line_number: line_number + num_lines,
+            value
+        },
+
+        GeckoCodeConversionError::Truncated { line_number, opcode } => GeckoCodeConversionError::Truncated {
+            line_number: line_number + num_lines,
+            opcode
+        },
+
+        err => err
+    }
+}
+
+/// Parses a gecko code into a list of structured codes.
+pub fn parse_gecko_code(gecko_code: &[u32]) -> Result<Vec<GeckoCode>, GeckoCodeConversionError> {
+    let mut codes: Vec<GeckoCode> = Vec::new();
+
+    let mut position = 0;
+
+    while position < gecko_code.len() {
+        // report the line number within the whole gecko code
+        let (code, num_values) = GeckoCode::parse(&gecko_code[position..])
+            .map_err(|err| offset_line_number(err, position / 2))?;
+
+        codes.push(code);
+        position += num_values;
+    }
+
+    Ok(codes)
+}
+
+/* Region Comparison */
+
+/// A code that doesn't match between two regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionDifference {
+    /// The index of the code within both lists.
+    pub index: usize,
+    pub reason: String
+}
+
+/// Compares the same gecko code for two regions of a game, where each address in
+/// `second` is expected to be `offset` bytes away from the address in `first`.
+/// Branch targets are expected to move by the same offset.
+/// ## Returns
+/// The codes that differ by more than the offset. The codes are equivalent if there are none.
+pub fn compare_regions(first: &[u32], second: &[u32], offset: i32) -> Result<Vec<RegionDifference>, GeckoCodeConversionError> {
+    let first = parse_gecko_code(first)?;
+    let second = parse_gecko_code(second)?;
+
+    let mut differences: Vec<RegionDifference> = Vec::new();
+
+    for (index, (first, second)) in first.iter().zip(&second).enumerate() {
+        // move the first code to where it's expected to be in the second region
+        let mut expected = first.clone();
+
+        if let Some(address) = expected.address_mut() {
+            *address = address.wrapping_add_signed(offset);
+        }
+
+        if let GeckoCode::CreateBranch { target, .. } = &mut expected {
+            *target = target.wrapping_add_signed(offset);
+        }
+
+        if expected == *second {
+            continue;
+        }
+
+        let reason = if std::mem::discriminant(first) != std::mem::discriminant(second) {
+            "The code types differ.".to_string()
+        } else if expected.address() != second.address() {
+            format!(
+                "Expected address 0x{:08X}, found 0x{:08X}.",
+                expected.address().unwrap_or_default(),
+                second.address().unwrap_or_default()
+            )
+        } else {
+            "The contents differ.".to_string()
+        };
+
+        differences.push(RegionDifference { index, reason });
+    }
+
+    if first.len() != second.len() {
+        differences.push(RegionDifference {
+            index: first.len().min(second.len()),
+            reason: format!("The number of codes differs ({} and {}).", first.len(), second.len())
+        });
+    }
+
+    Ok(differences)
+}
+
+/* Memory Map */
+
+/// The bytes covered by each cell of the memory map.
+const MEMORY_MAP_CELL_SIZE: u32 = 0x10000;
+
+/// The number of cells in each row of the memory map.
+const MEMORY_MAP_ROW_LENGTH: u32 = 64;
+
+/// Returns the range of addresses that each code writes to, in order.
+/// Codes that don't write to a known address are skipped, including
+/// the ones relative to the base address once it may have moved.
+pub fn write_ranges(gecko_code: &[u32]) -> Result<Vec<Range<u32>>, GeckoCodeConversionError> {
+    let mut base_address_changed = false;
+
+    let ranges = parse_gecko_code(gecko_code)?
+        .iter()
+        .filter_map(|code| {
+            let known = !(base_address_changed && is_base_address_relative(code));
+            base_address_changed = moves_base_address(code, base_address_changed);
+
+            if !known {
+                return None;
+            }
+
+            let (address, size) = match code {
+                GeckoCode::Fill8 { address, count, .. } => (*address, *count as u32 + 1),
+                GeckoCode::Fill16 { address, count, .. } => (*address, (*count as u32 + 1) * 2),
+                GeckoCode::Write32 { address, .. } => (*address, 4),
+                GeckoCode::StringWrite { address, bytes } => (*address, bytes.len() as u32),
+
+                GeckoCode::SerialWrite { address, value_size, num_additional_writes, address_increment, .. } => {
+                    let value_size = 1 << (*value_size).min(2);
+                    (*address, *num_additional_writes as u32 * *address_increment as u32 + value_size)
+                }
+
+                // only when storing to an absolute address
+                GeckoCode::StoreGeckoRegister { address, address_type: 0, value_size, num_additional_written_values, .. } => {
+                    (*address, (*num_additional_written_values as u32 + 1) << (*value_size).min(2))
+                }
+
+                _ => return None
+            };
+
+            Some(address..address.saturating_add(size))
+        })
+        .collect();
+
+    Ok(ranges)
+}
+
+/// Renders the given region of memory, marking each cell that `ranges` write to with `#`.
+fn memory_map_region(name: &str, region: Range<u32>, ranges: &[Range<u32>]) -> String {
+    let mut result = format!("// {name} (each column is 0x{:X} bytes)\n", MEMORY_MAP_CELL_SIZE);
+
+    let row_size = MEMORY_MAP_CELL_SIZE * MEMORY_MAP_ROW_LENGTH;
+
+    for row_start in region.step_by(row_size as usize) {
+        let cells = (0..MEMORY_MAP_ROW_LENGTH)
+            .map(|cell| {
+                let start = row_start + cell * MEMORY_MAP_CELL_SIZE;
+                let end = start + MEMORY_MAP_CELL_SIZE;
+
+                if ranges.iter().any(|range| range.start < end && start < range.end) {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect::<String>();
+
+        result += &format!("// 0x{:08X} |{cells}|\n", row_start);
+    }
+
+    result
+}
+
+/// Renders a map of MEM1 showing which parts of it a gecko code writes to.
+/// MEM2 is also shown if the code writes to it.
+pub fn memory_map(gecko_code: &[u32]) -> Result<String, GeckoCodeConversionError> {
+    const MEM1: Range<u32> = 0x80000000..0x81800000;
+    const MEM2: Range<u32> = 0x90000000..0x94000000;
+
+    let ranges = write_ranges(gecko_code)?;
+
+    let mut result = memory_map_region("MEM1", MEM1, &ranges);
+
+    if ranges.iter().any(|range| range.start < MEM2.end && MEM2.start < range.end) {
+        result += "\n";
+        result += &memory_map_region("MEM2", MEM2, &ranges);
+    }
+
+    Ok(result)
+}
+
+/* Action Replay */
+
+/// Converts a gecko code into the equivalent (unencrypted) Action Replay code.
+///
+/// The gecko write codes were based on Action Replay's, so they map over closely:
+/// - `0x00` (8-bit fill) becomes the AR 8-bit write and fill, `00XXXXXX ZZZZZZYY`,
+///   which has a 24-bit count below the value instead of gecko's 16-bit count above it
+/// - `0x02` (16-bit fill) becomes the AR 16-bit write and fill, `02XXXXXX ZZZZYYYY`
+/// - `0x04` (32-bit write) becomes the AR 32-bit write, `04XXXXXX YYYYYYYY`
+///
+/// The `larger_address` flag is the lowest bit of AR's 25-bit address,
+/// so these have the same encoding in both formats.
+/// Every other code type has no AR equivalent, and is an error.
+pub fn to_action_replay(gecko_code: &[u32]) -> Result<Vec<u32>, GeckoCodeConversionError> {
+    let mut result: Vec<u32> = Vec::new();
+
+    let mut position = 0;
+
+    for code in parse_gecko_code(gecko_code)? {
+        let values = code.encode()?;
+
+        match code {
+            GeckoCode::Fill8 { count, value, .. } => result.extend([values[0], ((count as u32) << 0x8) | value as u32]),
+
+            GeckoCode::Fill16 { .. } | GeckoCode::Write32 { .. } => result.extend(&values),
+
+            _ => return Err(GeckoCodeConversionError::NoActionReplayEquivalent {
+                line_number: (position / 2) + 1,
+                code_type: (values[0] >> 0x18) as u8
+            })
+        }
+
+        position += values.len();
+    }
+
+    Ok(result)
+}
+
+
+/// Returns a row of the write table for a code, if it's part of the write family.
+/// The columns are the address, the size of each value in bytes, the number of values, and the value.
+fn write_table_row(code: &GeckoCode) -> Option<[String; 4]> {
+    let row = match code {
+        GeckoCode::Fill8 { address, count, value } => [
+            format!("0x{:08X}", address),
+            "1".to_string(),
+            (*count as u32 + 1).to_string(),
+            format!("0x{:02X}", value)
+        ],
+
+        GeckoCode::Fill16 { address, count, value } => [
+            format!("0x{:08X}", address),
+            "2".to_string(),
+            (*count as u32 + 1).to_string(),
+            format!("0x{:04X}", value)
+        ],
+
+        GeckoCode::Write32 { address, value } => [
+            format!("0x{:08X}", address),
+            "4".to_string(),
+            "1".to_string(),
+            format!("0x{:08X}", value)
+        ],
+
+        GeckoCode::StringWrite { address, bytes } => {
+            // only show the start of long contents
+            let mut value = bytes
+                .iter()
+                .take(8)
+                .map(|byte| format!("{:02X}", byte))
+                .collect::<String>();
+
+            if bytes.len() > 8 {
+                value += "...";
+            }
+
+            [
+                format!("0x{:08X}", address),
+                bytes.len().to_string(),
+                "1".to_string(),
+                value
+            ]
+        }
+
+        GeckoCode::SerialWrite {
+            address,
+            value,
+            value_size,
+            num_additional_writes,
+            address_increment,
+            value_increment
+        } => {
+            let (size, value) = match value_size {
+                0 => (1, format!("0x{:02X}", value & 0xFF)),
+                1 => (2, format!("0x{:04X}", value & 0xFFFF)),
+                2 => (4, format!("0x{:08X}", value)),
+                _ => return None
+            };
+
+            [
+                format!("0x{:08X}", address),
+                size.to_string(),
+                (*num_additional_writes as u32 + 1).to_string(),
+                format!("{value} (+0x{:X} every 0x{:X} bytes)", value_increment, address_increment)
+            ]
+        }
+
+        GeckoCode::StoreGeckoRegister {
+            pointer,
+            value_size,
+            address_type,
+            num_additional_written_values,
+            register,
+            address
+        } => {
+            let size = match value_size {
+                0 => 1,
+                1 => 2,
+                2 => 4,
+                _ => return None
+            };
+
+            let address = match (address_type, pointer) {
+                (0, _) => format!("0x{:08X}", address),
+                (1, false) => format!("0x{:08X} + ba", address),
+                (1, true) => format!("0x{:08X} + po", address),
+                _ => return None
+            };
+
+            [
+                address,
+                size.to_string(),
+                (*num_additional_written_values as u32 + 1).to_string(),
+                format!("gr{register}")
+            ]
+        }
+
+        _ => return None
+    };
+
+    Some(row)
+}
+
+/// Returns the rows of the write table, aligned into columns with a header row.
+fn format_write_table(rows: &[[String; 4]]) -> String {
+    let header = ["Address", "Size", "Count", "Value"].map(String::from);
+
+    // find the width of each column
+    let mut widths = header.clone().map(|column| column.len());
+
+    for row in rows {
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.len());
+        }
+    }
+
+    let mut result = String::new();
+
+    for row in std::iter::once(&header).chain(rows) {
+        let columns = row
+            .iter()
+            .zip(widths)
+            .map(|(column, width)| format!("{column:<width$}"))
+            .collect::<Vec<String>>();
+
+        result += &format!("// {}\n", columns.join("  ").trim_end());
+    }
+
+    result
+}
+
+/// Converts a gecko code, showing runs of writes as an aligned table
+/// of addresses, sizes, counts and values. Other codes are converted as usual.
+pub fn convert_to_table(gecko_code: &[u32]) -> Result<String, GeckoCodeConversionError> {
+    let mut result = String::new();
+
+    let mut rows: Vec<[String; 4]> = Vec::new();
+
+    for code in convert_codes(gecko_code)? {
+        let row = GeckoCode::parse(&gecko_code[code.span.clone()])
+            .ok()
+            // a write relative to a base address that may have moved has no row
+            .filter(|(parsed, _)| code.address.is_some() || !is_base_address_relative(parsed))
+            .and_then(|(parsed, _)| write_table_row(&parsed));
+
+        if let Some(row) = row {
+            rows.push(row);
+            continue;
+        }
+
+        // this code isn't part of a table, so end the current one
+        if !rows.is_empty() {
+            result += &format_write_table(&rows);
+            result += "\n// ---\n\n";
+            rows.clear();
+        }
+
+        result += &code.text;
+        result += "\n\n// ---\n\n";
+    }
+
+    if !rows.is_empty() {
+        result += &format_write_table(&rows);
+        result += "\n// ---\n\n";
+    }
+
+    Ok(result)
+}
+
+/// Returns the last line of a converted code, which is where it's summarized,
+/// without its indentation. Warnings that were added after the code are skipped.
+fn summary_line(text: &str) -> &str {
+    text
+        .lines()
+        .map(str::trim_start)
+        .rfind(|line| !line.starts_with("// WARNING:"))
+        .unwrap_or_default()
+}
+
+/// Converts a gecko code into one tab-separated record per code, following a header record.
+/// The fields are the line number, the code type, the address, the size of each value
+/// in bytes, and the value. Codes that aren't writes leave the size empty and
+/// summarize the code instead, and assembly codes are summarized by their number of lines.
+pub fn convert_to_records(gecko_code: &[u32]) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "line\ttype\taddress\tsize\tvalue\n".to_string();
+
+    for code in convert_codes(gecko_code)? {
+        let address = code.address.map(|address| format!("0x{:08X}", address)).unwrap_or_default();
+        let code_type = code_type_short_name(code.code_type).unwrap_or("unknown");
+
+        let row = GeckoCode::parse(&gecko_code[code.span.clone()])
+            .ok()
+            // a write relative to a base address that may have moved has no row
+            .filter(|(parsed, _)| code.address.is_some() || !is_base_address_relative(parsed))
+            .and_then(|(parsed, _)| write_table_row(&parsed));
+
+        let (address, size, value) = match row {
+            // the address of a store can be relative, so the row's own address is used
+            Some([address, size, count, value]) => {
+                let value = if count == "1" { value } else { format!("{value} x{count}") };
+                (address, size, value)
+            }
+
+            None if code_category(code.code_type) == CodeCategory::Assembly => {
+                (address, String::new(), format!("{} lines", code.span.len() / 2 - 1))
+            }
+
+            // the last line is where the code is summarized
+            None => {
+                let summary = summary_line(&code.text);
+                (address, String::new(), summary.trim_start_matches("//").trim().to_string())
+            }
+        };
+
+        result += &format!("{}\t{code_type}\t{address}\t{size}\t{value}\n", code.line_number);
+    }
+
+    Ok(result)
+}
+
+/* Conditional Tree */
+
+/// Renders only the conditional structure of a gecko code as an indented tree.
+/// Each conditional is a node holding the codes it guards, which are summarized
+/// as leaves by their type and address. An else is a node of its own, and the
+/// other terminators only show up as the end of the nodes they close.
+pub fn conditional_tree(gecko_code: &[u32]) -> Result<String, GeckoCodeConversionError> {
+    let mut result = String::new();
+
+    for code in convert_codes(gecko_code)? {
+        let (parsed, _) = GeckoCode::parse(&gecko_code[code.span.clone()])?;
+        let indent = "  ".repeat(code.depth);
+
+        let line = match parsed {
+            GeckoCode::If32 { .. } | GeckoCode::If16 { .. } | GeckoCode::IfCounter { .. } => {
+                // the condition is the last line of the converted code, without the opening brace
+                let condition = summary_line(&code.text);
+                let condition = condition.trim_start_matches("//").trim_start();
+
+                match condition.split_once(" {") {
+                    Some((condition, _)) => condition.to_string(),
+                    None => condition.to_string()
+                }
+            }
+
+            GeckoCode::Endif { is_else: true, .. } => "else".to_string(),
+
+            GeckoCode::FullTerminator { .. } | GeckoCode::Endif { .. } => continue,
+
+            _ => {
+                let name = code_type_name(code.code_type).unwrap_or("Unknown");
+
+                match code.address {
+                    Some(address) => format!("- {name} (0x{:08X})", address),
+                    None => format!("- {name}")
+                }
+            }
+        };
+
+        result += &format!("// {indent}{line}\n");
+    }
+
+    Ok(result)
+}
+
+
+/* Code Types */
+
+/// Returns the line showing the range that a fill of `count` starting at `address` covers.
+/// The end is clamped to the end of the address space instead of wrapping around.
+/// The range is relative to `ba` once it may have moved from where it starts.
+fn fill_range(address: u32, count: u32, base_address_changed: bool) -> String {
+    if base_address_changed {
+        let end = address + count + 1;
+        return format!("// Range: {} to {}\n", target_address(address, true), target_address(end, true));
+    }
+
+    match address.checked_add(count).and_then(|end| end.checked_add(1)) {
+        Some(end) => format!("// Range: 0x{:08X} to 0x{:08X}\n", address, end),
+        None => format!("// Range: 0x{:08X} to 0x{:08X} // (range clamped)\n", address, u32::MAX)
+    }
+}
+
+/// # 0x00: 8-bit RAM Write & Fill
+/// The `value` will **constantly** fill the range `address`
+/// to `address + count + 1`.
+/// ## Parameters
+/// `address`: The address to start filling at.
+/// `count`: The number of bytes to fill after the first.
+/// `value`: The value to fill with.
+/// `base_address_changed`: Indicates if the address relative to `ba` isn't known.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_00(address: u32, count: u16, value: u8, base_address_changed: bool) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - Constant 8-bit RAM Fill -\n".to_string();
+    result += &fill_range(address, count as u32, base_address_changed);
+    result += &format!("// Value: 0x{:02X}", value);
+
+    Ok(result)
+}
+
+/// # 0x02: 16-bit RAM Write & Fill
+/// The `value` will **constantly** fill the range
+/// `address` to `address + count + 1`.
+/// ## Parameters
+/// `address`: The address to start filling at.
+/// `count`: The number of values to fill after the first.
+/// `value`: The value to fill with.
+/// `base_address_changed`: Indicates if the address relative to `ba` isn't known.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_02(address: u32, count: u16, value: u16, base_address_changed: bool) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - Constant 16-bit RAM Fill -\n".to_string();
+    result += &fill_range(address, count as u32, base_address_changed);
+    result += &format!("// Value: 0x{:04X}", value);
+    
+    Ok(result)
+}
+
+/// # 0x04: 32-bit RAM Write
+/// The specified `value` will **constantly** be
+/// written to `address`.
+/// ## Parameters
+/// `address`: The address to write to.
+/// `value`: The value to write.
+/// `base_address_changed`: Indicates if the address relative to `ba` isn't known.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_04(address: u32, value: u32, base_address_changed: bool) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - Constant 32-bit RAM Write -\n".to_string();
+    result += &format!("// Target address: {}\n", target_address(address, base_address_changed));
+    result += &format!("// Value: 0x{:08X}", value);
+    Ok(result)
+}
+
+/// # 0x06: String RAM Write
+/// The following `count` bytes will be written to `address`.
+/// ### Note
+/// The name of the code type is "String" RAM Write, but
+/// there is no null-termination check to ensure that the
+/// contents are actually a valid string. In other words,
+/// this code type can simply be used to write raw bytes,
+/// regardless of content.
+/// ## Parameters
+/// `address`: The address to write to.
+/// `raw_bytes`: The bytes to write.
+/// `base_address_changed`: Indicates if the address relative to `ba` isn't known.
+/// `options`: The options used for conversion.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_06(address: u32, raw_bytes: &[u8], base_address_changed: bool, options: &ConversionOptions) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - String RAM Write - \n".to_string();
+    result += &format!("// Target address: {}\n", target_address(address, base_address_changed));
+
+    // determine if the bytes can be output as a string
+    // or if they should be output as-is
+    let mut is_string = false;
+
+    if let Some(index) = raw_bytes
+        .iter()
+        .position(|byte| *byte == 0)
+    {
+        if index >= raw_bytes.len() - 1 {
+            // the only 0 is at the end; this can
+            // be considered a *candidate* for 
+            // a valid string
+            is_string = true;
+        }
+    }
+
+    // determine if the string was valid and printable
+    let mut printed_string = false;
+
+    if is_string {
+        // try to convert it to a string
+        if let Ok(string) = std::str::from_utf8(raw_bytes) {
+            printed_string = true;
+            result += &format!("// String contents: \"{string}\"\n");
+        }
+    }
+
+    // text without a terminator, such as text written into a fixed-size field
+    if !printed_string && !raw_bytes.is_empty() && raw_bytes.iter().all(|byte| byte.is_ascii_graphic() || *byte == b' ') {
+        printed_string = true;
+        result += &format!("// ASCII (no terminator): \"{}\"\n", String::from_utf8_lossy(raw_bytes));
+    }
+
+    if raw_bytes.is_empty() {
+        // nothing is written, so there's nothing to dump
+        result += "// Byte contents: (no bytes)\n";
+    } else if !printed_string {
+        // not a string or the string wasn't printable
+        // print out bytes instead
+        
+        result += "// Byte contents:\n// [";
+
+        // the number of bytes that will be printed on one line
+        // before moving to the next. each byte takes up 6 columns
+        // ("0x00, "), after the 4-column "// [" at the start
+        let mut num_printed_bytes = match options.max_columns {
+            Some(max_columns) => (max_columns.max(MIN_COLUMNS) - 4) / 6,
+            None => 8
+        };
+
+        let group_size = options.byte_group_size.filter(|size| *size > 0);
+
+        if let Some(group_size) = group_size {
+            // keep whole groups on each line, making room
+            // for the extra space between each group
+            if let Some(max_columns) = options.max_columns {
+                let available_columns = max_columns.max(MIN_COLUMNS) - 4;
+
+                while num_printed_bytes > 1 && (num_printed_bytes * 6) + (num_printed_bytes - 1) / group_size > available_columns {
+                    num_printed_bytes -= 1;
+                }
+            }
+
+            if num_printed_bytes >= group_size {
+                num_printed_bytes -= num_printed_bytes % group_size;
+            }
+        }
+        
+        for (index, byte) in raw_bytes.iter().enumerate() {
+
+            if index != 0 && index % num_printed_bytes == 0 {
+                result += "\n// ";
+            }
+
+            // check if this is the last one
+            if index == raw_bytes.len() - 1 {
+                result += &format!("0x{:02X}]", byte);
+            } else {
+                result += &format!("0x{:02X}, ", byte);
+
+                // separate groups, unless this is the end of the line
+                let num_bytes = index + 1;
+
+                if group_size.is_some_and(|size| num_bytes % size == 0) && num_bytes % num_printed_bytes != 0 {
+                    result += " ";
+                }
+            }
+        }
+    }
+    
+    
+    Ok(result)
+}
+
+/// Returns the operand of a base address or pointer offset code (`0x4X`, `0x5X`),
+/// which is `value` with `ba` or `po` and a gecko register optionally added.
+/// The `0x5X` codes add `po` instead of `ba`.
+/// ## Returns
+/// The operand, along with `true` if the operation adds to the
+/// base address or pointer offset instead of replacing it.
+fn address_operation_operand(code_type: u8, flags: u16, register: u8, value: u32) -> (String, bool) {
+    let add = (flags & 0xF000) != 0;
+    let add_address = (flags & 0x0F00) != 0;
+    let add_register = (flags & 0x00F0) != 0;
+    let register = register & 0xF;
+
+    let mut operand = format!("0x{:08X}", value);
+
+    if add_address {
+        operand += if code_type & 0x10 != 0 { " + po" } else { " + ba" };
+    }
+
+    if add_register {
+        operand += &format!(" + gr{register}");
+    }
+
+    (operand, add)
+}
+
+/// Returns the name of the register a base address or pointer offset code
+/// operates on, along with the name of the register as it appears in the output.
+fn address_operation_register(code_type: u8) -> (&'static str, &'static str) {
+    if code_type & 0x08 != 0 {
+        ("Pointer Offset", "po")
+    } else {
+        ("Base Address", "ba")
+    }
+}
+
+/// # 0x40: Load into Base Address
+/// The base address is set to (or has added to it) the 32-bit value at `address`,
+/// optionally adding the base address or pointer offset and a gecko register to `address`.
+/// `0x48` loads into the pointer offset instead, and `0x5X` adds the pointer offset to `address`.
+/// ## Parameters
+/// `code_type`: The code type, which holds the register and what's added.
+/// `flags`: The bits between the code type and the gecko register.
+/// `register`: The gecko register that can be added.
+/// `address`: The address of the value.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_40_50(code_type: u8, flags: u16, register: u8, address: u32) -> Result<String, GeckoCodeConversionError> {
+    let (name, short_name) = address_operation_register(code_type);
+    let (operand, add) = address_operation_operand(code_type, flags, register, address);
+    let operator = if add { "+=" } else { "=" };
+
+    let mut result = format!("// - Load into {name} -\n");
+    result += &format!("// {short_name} {operator} *({operand})");
+
+    Ok(result)
+}
+
+/// # 0x42: Set Base Address to
+/// The base address is set to (or has added to it) `value`,
+/// optionally adding the base address or pointer offset and a gecko register.
+/// `0x4A` sets the pointer offset instead, and `0x5X` adds the pointer offset.
+/// ## Parameters
+/// `code_type`: The code type, which holds the register and what's added.
+/// `flags`: The bits between the code type and the gecko register.
+/// `register`: The gecko register that can be added.
+/// `value`: The value to set it to.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_42_52(code_type: u8, flags: u16, register: u8, value: u32) -> Result<String, GeckoCodeConversionError> {
+    let (name, short_name) = address_operation_register(code_type);
+    let (operand, add) = address_operation_operand(code_type, flags, register, value);
+    let operator = if add { "+=" } else { "=" };
+
+    let mut result = format!("// - Set {name} to -\n");
+    result += &format!("// {short_name} {operator} {operand}");
+
+    Ok(result)
+}
+
+
+/// # 0x08: Serial RAM Write
+/// `value` is written to `address`, then written again for each additional
+/// write, adding `value_increment` to the value and `address_increment`
+/// to the address each time.
+/// ## Parameters
+/// `address`: The address of the first write.
+/// `value`: The first value to write.
+/// `value_size`: 0 for 8-bit, 1 for 16-bit and 2 for 32-bit values.
+/// `num_additional_writes`: The number of writes after the first.
+/// `address_increment`: What's added to the address after each write.
+/// `value_increment`: What's added to the value after each write.
+/// `base_address_changed`: Indicates if the address relative to `ba` isn't known.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_08(
+    address: u32,
+    value: u32,
+    value_size: u8,
+    num_additional_writes: u16,
+    address_increment: u16,
+    value_increment: u32,
+    base_address_changed: bool
+) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - Serial RAM Write -\n".to_string();
+
+    let num_writes = num_additional_writes as u32 + 1;
+
+    // only the bytes that are written are part of the value
+    let (bits, value) = match value_size {
+        0 => (8, format!("0x{:02X}", value & 0xFF)),
+        1 => (16, format!("0x{:04X}", value & 0xFFFF)),
+        2 => (32, format!("0x{:08X}", value)),
+        _ => return Err(GeckoCodeConversionError::Malformed)
+    };
+
+    result += &format!(
+        "// Writes {num_writes} {bits}-bit values starting at {}, value {value} incrementing by 0x{:X} every 0x{:X} bytes",
+        target_address(address, base_address_changed), value_increment, address_increment
+    );
+
+    Ok(result)
+}
+
+/// # 0x20: 32-bit If
+/// The following codes are only executed if the 32-bit value at
+/// `address` compares to `value`, until the next terminator.
+/// If the lowest bit of the address is set, the previous if ends first.
+/// The `0x3X` codes use an address relative to `po` instead.
+/// ## Parameters
+/// `pointer`: Indicates if `address` is relative to `po`.
+/// `comparison`: How the value is compared.
+/// `endif_first`: Indicates if the previous if ends first.
+/// `address`: The address of the value.
+/// `value`: The value to compare to.
+/// `base_address_changed`: Indicates if the address relative to `ba` isn't known.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_20(pointer: bool, comparison: Comparison, endif_first: bool, address: u32, value: u32, base_address_changed: bool) -> Result<String, GeckoCodeConversionError> {
+    let (mut result, target) = conditional_header("32", comparison, pointer, endif_first, address, base_address_changed);
+
+    result += &format!("// if (*{target} {} 0x{:08X}) {{", comparison.operator(), value);
+
+    if matches!(comparison, Comparison::Greater | Comparison::Lower) {
+        result += " // unsigned";
+    }
+
+    Ok(result)
+}
+
+/// # 0x28: 16-bit If
+/// The following codes are only executed if the 16-bit value at `address`,
+/// ignoring the bits set in `mask`, compares to `value`, until the next terminator.
+/// If the lowest bit of the address is set, the previous if ends first.
+/// The `0x3X` codes use an address relative to `po` instead.
+/// ## Parameters
+/// `pointer`: Indicates if `address` is relative to `po`.
+/// `comparison`: How the value is compared.
+/// `endif_first`: Indicates if the previous if ends first.
+/// `address`: The address of the value.
+/// `mask`: The bits of the value that are ignored.
+/// `value`: The value to compare to.
+/// `base_address_changed`: Indicates if the address relative to `ba` isn't known.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_28(
+    pointer: bool,
+    comparison: Comparison,
+    endif_first: bool,
+    address: u32,
+    mask: u16,
+    value: u16,
+    base_address_changed: bool
+) -> Result<String, GeckoCodeConversionError> {
+    let (mut result, target) = conditional_header("16", comparison, pointer, endif_first, address, base_address_changed);
+
+    if mask == 0 {
+        result += &format!("// if (*{target} {} 0x{:04X}) {{", comparison.operator(), value);
+    } else {
+        result += "// (the bits set in the mask are ignored)\n";
+        result += &format!("// if ((*{target} & ~0x{:04X}) {} 0x{:04X}) {{", mask, comparison.operator(), value);
+    }
+
+    if matches!(comparison, Comparison::Greater | Comparison::Lower) {
+        result += " // unsigned";
+    }
+
+    Ok(result)
+}
+
+/// Returns the start of a converted conditional code, along with the address it checks.
+/// The address is only shown relative to `ba` once it may have moved from where it starts.
+fn conditional_header(bits: &str, comparison: Comparison, pointer: bool, endif_first: bool, address: u32, base_address_changed: bool) -> (String, String) {
+    let mut result = format!("// - {bits}-bit If {} -\n", comparison.name());
+
+    if endif_first {
+        result += "// (ends the previous if first)\n";
+    }
+
+    let target = if pointer {
+        format!("(po + 0x{:X})", address)
+    } else {
+        target_address(address, base_address_changed)
+    };
+
+    (result, target)
+}
+
+/// Returns the operator of a gecko register operation,
+/// along with a note about how it's done, if it needs one.
+fn register_operation(operation: u8) -> Option<(&'static str, Option<&'static str>)> {
+    let result = match operation {
+        0x0 => ("+", None),
+        0x1 => ("*", None),
+        0x2 => ("|", None),
+        0x3 => ("&", None),
+        0x4 => ("^", None),
+        0x5 => ("<<", None),
+        0x6 => (">>", None),
+        0x7 => ("rotl", None),
+        0x8 => (">>", Some("arithmetic")),
+        0x9 => ("+", Some("single-precision float")),
+        0xA => ("*", Some("single-precision float")),
+        _ => return None
+    };
+
+    Some(result)
+}
+
+/// # 0x86: Gecko Register Operation
+/// The gecko register is set to the result of an operation between itself
+/// and `value`. Either operand can be used as the address of the value instead.
+/// ## Parameters
+/// `operation`: Which operation is done.
+/// `flags`: Which of the operands are used as addresses.
+/// `register`: The gecko register.
+/// `value`: The other operand.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_86(operation: u8, flags: u8, register: u8, value: u32) -> Result<String, GeckoCodeConversionError> {
+    let Some((operator, note)) = register_operation(operation) else {
+        return Err(GeckoCodeConversionError::Malformed);
+    };
+
+    let left = if flags & 1 != 0 { format!("*gr{register}") } else { format!("gr{register}") };
+    let right = if flags & 2 != 0 { format!("*0x{:08X}", value) } else { format!("0x{:08X}", value) };
+
+    let mut result = format!("// gr{register} = {left} {operator} {right}");
+
+    if let Some(note) = note {
+        result += &format!(" // {note}");
+    }
+
+    Ok(result)
+}
+
+/// # 0x88: Gecko Register Operation
+/// The gecko register is set to the result of an operation between itself and
+/// another gecko register. Either operand can be used as the address of the value instead.
+/// ## Parameters
+/// `operation`: Which operation is done.
+/// `flags`: Which of the operands are used as addresses.
+/// `register`: The gecko register.
+/// `other_register`: The gecko register that's the other operand.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_88(operation: u8, flags: u8, register: u8, other_register: u8) -> Result<String, GeckoCodeConversionError> {
+    let Some((operator, note)) = register_operation(operation) else {
+        return Err(GeckoCodeConversionError::Malformed);
+    };
+
+    let left = if flags & 1 != 0 { format!("*gr{register}") } else { format!("gr{register}") };
+    let right = if flags & 2 != 0 { format!("*gr{other_register}") } else { format!("gr{other_register}") };
+
+    let mut result = format!("// gr{register} = {left} {operator} {right}");
+
+    if let Some(note) = note {
+        result += &format!(" // {note}");
+    }
+
+    Ok(result)
+}
+
+/// Returns the name of a register used by a memory copy code,
+/// with `offset` added to it. `0xF` is the base address.
+fn memory_copy_operand(register: u8, offset: Option<u32>) -> String {
+    let mut result = match register {
+        0xF => "ba".to_string(),
+        _ => format!("gr{register}")
+    };
+
+    if let Some(offset) = offset.filter(|offset| *offset != 0) {
+        result += &format!(" + 0x{:08X}", offset);
+    }
+
+    result
+}
+
+/// # 0x8A: Memory Copy
+/// `count` bytes are copied from the address in the source register to the address
+/// in the destination register plus `offset`. `0x8C` adds `offset` to the source instead.
+/// ## Parameters
+/// `offset_source`: Indicates if `offset` is added to the source instead of the destination.
+/// `count`: The number of bytes to copy.
+/// `source`: The register holding the address to copy from.
+/// `destination`: The register holding the address to copy to.
+/// `offset`: What's added to one of the addresses.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_8a_8c(offset_source: bool, count: u16, source: u8, destination: u8, offset: u32) -> Result<String, GeckoCodeConversionError> {
+    let (source, destination) = if offset_source {
+        (memory_copy_operand(source, Some(offset)), memory_copy_operand(destination, None))
+    } else {
+        (memory_copy_operand(source, None), memory_copy_operand(destination, Some(offset)))
+    };
+
+    Ok(format!("// memcpy({destination}, {source}, 0x{:X})", count))
+}
+
+/// # 0xA8: 16-bit If Counter
+/// The following codes are only executed if the code's own counter,
+/// ignoring the bits set in `mask`, compares to `value`, until the next terminator.
+/// The counter is incremented each time the condition is true and reset when it's
+/// false, or reset when it's true if the `0x8` flag is set. This is used for codes
+/// that only take effect every so many frames.
+/// If the `0x1` flag is set, the previous if ends first.
+/// ## Parameters
+/// `comparison`: How the counter is compared.
+/// `endif_first`: Indicates if the previous if ends first.
+/// `reset`: Indicates if the counter is reset when the condition is true.
+/// `counter`: The value the counter starts at.
+/// `mask`: The bits of the counter that are ignored.
+/// `value`: The value to compare to.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_a8(comparison: Comparison, endif_first: bool, reset: bool, counter: u16, mask: u16, value: u16) -> Result<String, GeckoCodeConversionError> {
+    let mut result = format!("// - 16-bit If Counter {} -\n", comparison.name());
+
+    if endif_first {
+        result += "// (ends the previous if first)\n";
+    }
+
+    if reset {
+        result += "// (the counter is reset when the condition is true)\n";
+    } else {
+        result += "// (the counter is incremented when the condition is true)\n";
+    }
+
+    result += &format!("// Counter conditional: counter starts at 0x{:04X}\n", counter);
+
+    if mask == 0 {
+        result += &format!("// if (counter {} 0x{:04X}) {{", comparison.operator(), value);
+    } else {
+        result += &format!("// if ((counter & ~0x{:04X}) {} 0x{:04X}) {{", mask, comparison.operator(), value);
+    }
+
+    if matches!(comparison, Comparison::Greater | Comparison::Lower) {
+        result += " // unsigned";
+    }
+
+    Ok(result)
+}
+
+/// # 0x44: Store Base Address at
+/// The base address is written to `address`, optionally adding
+/// the base address or pointer offset and a gecko register to `address`.
+/// `0x4C` stores the pointer offset instead, and `0x5X` adds the pointer offset to `address`.
+/// ## Parameters
+/// `code_type`: The code type, which holds the register and what's added.
+/// `flags`: The bits between the code type and the gecko register.
+/// `register`: The gecko register that can be added.
+/// `address`: The address to store it at.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_44_54(code_type: u8, flags: u16, register: u8, address: u32) -> Result<String, GeckoCodeConversionError> {
+    let (name, short_name) = address_operation_register(code_type);
+    let (operand, _) = address_operation_operand(code_type, flags, register, address);
+
+    let mut result = format!("// - Store {name} at -\n");
+    result += &format!("// *({operand}) = {short_name}");
+
+    Ok(result)
+}
+
+/// # 0x46: Put Code Location into Base Address
+/// The base address is set to the address of the next line of the
+/// code list in memory, plus the signed 16-bit `offset`. This lets
+/// the following codes read and overwrite the code list itself.
+/// `0x4E` sets the pointer offset instead.
+/// ## Parameters
+/// `code_type`: The code type, which holds the register.
+/// `offset`: What's added to the address of the next line.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_46_4e(code_type: u8, offset: i16) -> Result<String, GeckoCodeConversionError> {
+    let (name, register) = address_operation_register(code_type);
+
+    let mut result = format!("// - Put Code Location into {name} -\n");
+
+    result += &match offset {
+        0 => format!("// {register} = address of next code line"),
+        _ => {
+            let sign = if offset < 0 { "-" } else { "+" };
+            format!("// {register} = address of next code line {sign} 0x{:X}", offset.unsigned_abs())
+        }
+    };
+
+    result += &format!("\n// Note: codes writing through {register} can overwrite the following lines' operands at runtime");
+
+    Ok(result)
+}
+
+/// # 0x60: Set Repeat
+/// The location of the next line and the number of times to repeat
+/// are stored in a block register. The matching `0x62` code jumps back
+/// to the next line until it has done so that many times.
+/// ## Parameters
+/// `count`: The number of times to repeat.
+/// `block`: The block register that holds the repeat.
+/// `target`: The line that the repeat jumps back to, which is the one after this code.
+/// `repeat_targets`: The line that each block's repeat jumps back to, which is set for this block.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_60(count: u16, block: u8, target: usize, repeat_targets: &mut [Option<usize>; 16]) -> Result<String, GeckoCodeConversionError> {
+    repeat_targets[block as usize & 0xF] = Some(target);
+
+    Ok(format!("// set repeat: 0x{:X} times, block {block} (repeats from line {target})", count))
+}
+
+/// # 0x62: Execute Repeat
+/// If the repeat stored in the block register hasn't run out,
+/// it's counted down and the code handler jumps back to the line after the `0x60` code.
+/// ## Parameters
+/// `block`: The block register that holds the repeat.
+/// `repeat_targets`: The line that each block's repeat jumps back to.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_62(block: u8, repeat_targets: &[Option<usize>; 16]) -> Result<String, GeckoCodeConversionError> {
+    let result = match repeat_targets[block as usize & 0xF] {
+        Some(target) => format!("// repeat block {block} (back to line {target})"),
+        None => format!("// repeat block {block} (no repeat was set for this block)")
+    };
+
+    Ok(result)
+}
+
+/// # 0x64: Return
+/// The code handler jumps to the line stored in a block register by a `0x68` code.
+/// `0x66` (Goto) instead jumps by a signed number of lines, and `0x68` (Gosub)
+/// also stores the location of the next line in a block register first.
+/// Each of these can be done always, or only if the current conditional is true or false.
+/// ## Parameters
+/// `code_type`: The code type, which is `0x64`, `0x66` or `0x68`.
+/// `condition`: 0 to always jump, 1 to only jump if the current conditional is true, or 2 if it's false.
+/// `offset`: The number of lines to jump by. This isn't used by `0x64`.
+/// `block`: The block register that's used. This isn't used by `0x66`.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_64_68(code_type: u8, condition: u8, offset: i16, block: u8) -> Result<String, GeckoCodeConversionError> {
+    let condition = match condition {
+        0 => "",
+        1 => " if the current conditional is true",
+        2 => " if the current conditional is false",
+        _ => return Err(GeckoCodeConversionError::Malformed)
+    };
+
+    let sign = if offset < 0 { "-" } else { "+" };
+    let lines = if offset.unsigned_abs() == 1 { "line" } else { "lines" };
+
+    let result = match code_type {
+        0x64 => format!("// return (block {block}){condition}"),
+        0x66 => format!("// goto {sign}{} {lines}{condition}", offset.unsigned_abs()),
+        _ => format!("// gosub {sign}{} {lines} (block {block}){condition}", offset.unsigned_abs())
+    };
+
+    Ok(result)
+}
+
+/// # 0x80: Set Gecko Register to
+/// The gecko register is set to (or has added to it) `value`,
+/// optionally adding the base address. `0x90` adds the pointer offset instead.
+/// There are 16 gecko registers, so only the lowest 4 bits choose the register.
+/// ## Parameters
+/// `pointer`: Indicates if `po` is added instead of `ba`.
+/// `flags`: The bits between the code type and the register.
+/// `register`: The gecko register.
+/// `value`: The value to set it to.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_80(pointer: bool, flags: u16, register: u8, value: u32) -> Result<String, GeckoCodeConversionError> {
+    let add = (flags & 0xF000) != 0;
+    let add_address = (flags & 0x0F00) != 0;
+    let register = register & 0xF;
+
+    let operator = if add { "+=" } else { "=" };
+    let mut result = format!("// gr{register} {operator} 0x{:08X}", value);
+
+    if add_address {
+        result += if pointer { " + po" } else { " + ba" };
+    }
+
+    Ok(result)
+}
+
+/// # 0x82: Load into Gecko Register
+/// ## Parameters
+/// `register`: The gecko register.
+/// `value`: The address of the value to load.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_82(register: u8, value: u32) -> Result<String, GeckoCodeConversionError> {
+    Ok(format!("// - Load value 0x{:08X} into register {register}", value))
+}
+
+/// # 0x84, 0x94: Store Gecko Register at
+/// ## Parameters
+/// `pointer`: Indicates if `po` is added instead of `ba`.
+/// `value_size`: 0 for 1-byte, 1 for 2-byte and 2 for 4-byte values.
+/// `address_type`: 0 if `address` is used as-is, or 1 if `ba` or `po` is added to it.
+/// `num_additional_written_values`: The number of values written after the first.
+/// `register`: The gecko register.
+/// `address`: The address to store it at.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_84_94(
+    pointer: bool,
+    value_size: u8,
+    address_type: u8,
+    num_additional_written_values: u16,
+    register: u8,
+    address: u32
+) -> Result<String, GeckoCodeConversionError> {
+    let value_size = match value_size {
+        0 => 1,
+        1 => 2,
+        2 => 4,
+        _ => {
+            let err = GeckoCodeConversionError::ParseError {
+                reason: "Invalid T type. Must be 0 (1 byte), 1 (2 bytes), or 2 (4 bytes).".to_string()
+            };
+
+            return Err(err);
+        }
+    };
+
+    // the total number of consecutive written values is (num_additional_written_values + 1)
+    let consecutive_written = num_additional_written_values + 1;
+
+    // determine whether the address is used as-is, or
+    // if it's an offset from the base address/pointer
+    let target = match (pointer, address_type) {
+        (_, 0) => format!("0x{:08X}", address),
+        (false, 1) => format!("0x{:08X} + ba", address),
+        (true, 1) => format!("0x{:08X} + po", address),
+
+        _ => {
+            let err = GeckoCodeConversionError::ParseError {
+                reason: "Invalid Y type. Must be 0 (address) or 1 (ba/po + address).".to_string()
+            };
+
+            return Err(err);
+        }
+    };
+
+    let result = format!("// - Store register {register} starting at address {target} with {consecutive_written} consecutive written {value_size}-byte values -");
+
+    Ok(result)
+}
+
+/// Returns the note for an instruction of an assembly block that invalidates
+/// the instruction cache after an earlier instruction of the block wrote to memory,
+/// which is how a block makes the game see the instructions it patched.
+fn self_modification_note(code: u32, has_written: bool) -> &'static str {
+    if has_written && ppc::is_instruction_cache_invalidate(code) {
+        " // invalidates I-cache after self-modification"
+    } else {
+        ""
+    }
+}
+
+/// Returns the prefix for the instruction at `index` (starting from 0) of an assembly block.
+fn instruction_prefix(index: usize, options: &ConversionOptions) -> String {
+    if options.number_instructions {
+        format!("{:02}: ", index + 1)
+    } else {
+        String::new()
+    }
+}
+
+/// Formats a number of cycles, such as `1 cycle` or `3 cycles`.
+fn cycle_count(cycles: u32) -> String {
+    match cycles {
+        1 => "1 cycle".to_string(),
+        _ => format!("{cycles} cycles")
+    }
+}
+
+/// Returns the estimated cycle count to show after an instruction of an assembly block.
+fn cycle_note(code: u32, options: &ConversionOptions) -> String {
+    if options.cycle_estimates {
+        format!(" // ~{}", cycle_count(ppc::estimated_cycles(code)))
+    } else {
+        String::new()
+    }
+}
+
+/// Returns the line showing the estimated cycle count of a whole assembly block.
+fn cycle_total(codes: &[u32], options: &ConversionOptions) -> String {
+    if options.cycle_estimates {
+        let total: u32 = codes.iter().map(|code| ppc::estimated_cycles(*code)).sum();
+        format!("// ~{}\n", cycle_count(total))
+    } else {
+        String::new()
+    }
+}
+
+/// # 0xC0: Execute Assembly
+/// The following `lines` of assembly will be executed.
+/// This MUST end with a `blr` instruction (`0x4E800020`).
+/// ## Parameters
+/// `instructions`: The values following the first line.
+/// `options`: The options used for conversion.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_c0(instructions: &[u32], options: &ConversionOptions) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - Execute Assembly - \n\n".to_string();
+
+    let returns = ends_in_return(instructions);
+    let codes = execute_assembly_instructions(instructions);
+
+    let mut has_written = false;
+
+    for (index, code) in codes.iter().enumerate() {
+        result += &format!(
+            "{}{}{}{}\n",
+            instruction_prefix(index, options),
+            ppc::code_to_instruction(*code),
+            self_modification_note(*code, has_written),
+            cycle_note(*code, options)
+        );
+
+        has_written |= ppc::writes_memory(*code);
+    }
+
+    result += &cycle_total(codes, options);
+
+    if !returns {
+        result += "// warning: Execute Assembly block does not end in blr\n";
+    }
+
+    Ok(result)
+}
+
+/// Returns if the last line of an Execute Assembly block returns.
+/// It must end on an instruction that always returns, which is
+/// usually `blr` but can be any branch to LR or CTR. Code after an
+/// earlier return can still be reached by branching to it.
+fn ends_in_return(values: &[u32]) -> bool {
+    values
+        .iter()
+        .rev()
+        .take(2)
+        .any(|code| ppc::is_unconditional_return(*code))
+}
+
+/// Returns the instructions of an Execute Assembly block,
+/// without the value that pads the last line after the return.
+fn execute_assembly_instructions(values: &[u32]) -> &[u32] {
+    match values.split_last() {
+        Some((0, instructions)) if ends_in_return(values) => instructions,
+        _ => values
+    }
+}
+
+/// Returns the instructions of an Insert Assembly block that ends with the terminator.
+/// The terminator isn't an instruction, and a `nop` before it only pads the last line.
+fn insert_assembly_instructions(values: &[u32]) -> &[u32] {
+    let values = match values.split_last() {
+        Some((0, instructions)) => instructions,
+        _ => values
+    };
+
+    match values.split_last() {
+        Some((0x60000000, instructions)) => instructions,
+        _ => values
+    }
+}
+/// # 0xC2: Insert Assembly
+/// A branch to a subroutine containing `code` will
+/// be placed at `address`. The code must end with
+/// `0x00000000`. If an additional line must be used
+/// to do this, use a `nop` (`0x60000000`). The value
+/// stored in the second value of the first line is
+/// the total number of *subsequent* lines. **The Gecko
+/// Code handler will automatically add a branch back to
+/// `address + 0x4`.** When the number of lines can't be
+/// right, the end of the code is guessed from its contents.
+/// ## Parameters
+/// `address`: The address to place the branch at.
+/// `num_lines`: The declared number of lines.
+/// `instructions`: The values following the first line, including the terminator.
+/// `options`: The options used for conversion.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_c2(address: u32, num_lines: u32, instructions: &[u32], options: &ConversionOptions) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - Insert Assembly -\n".to_string();
+    result += &format!("// Target address: 0x{:08X}\n", address);
+
+    // the parser only uses the declared lines if they end with the terminator
+    let uses_line_count = num_lines > 0
+        && instructions.len() == num_lines as usize * 2
+        && instructions.last() == Some(&0);
+
+    let codes = if uses_line_count {
+        result += "// note: using declared line count\n\n";
+        insert_assembly_instructions(instructions)
+    } else {
+        result += "// note: heuristic termination\n\n";
+
+        // the guessed end is either a line of `nop` and the terminator, or a `nop` on the right
+        match instructions {
+            [codes @ .., 0x60000000, 0] => codes,
+            [codes @ .., 0x60000000] => codes,
+            _ => instructions
+        }
+    };
+
+    let epilogue_length = if options.collapse_epilogue { ppc::standard_epilogue_length(codes) } else { 0 };
+
+    result += &format_block(codes, epilogue_length, options);
+
+    // the code handler returns to the instruction after the one that was replaced
+    if epilogue_length > 0 {
+        result += &format!("// restores LR and returns to 0x{:08X}\n", address + 4);
+    } else {
+        result += &format!("// (branch back to 0x{:08X})\n", address + 4);
+    }
+
+    Ok(result)
+}
+
+/// Converts a block of instructions into written lines. Relative branches
+/// that land inside the block are given local labels. The ones that leave it
+/// are marked as calls into the game's own code if they link, and as branches out otherwise.
+/// The last `num_hidden` instructions are left out, but can still be branched to.
+fn format_block(codes: &[u32], num_hidden: usize, options: &ConversionOptions) -> String {
+    let block_len = (codes.len() * 4) as i64;
+
+    // find where each relative branch lands, as an offset from the start of the block
+    let targets = codes
+        .iter()
+        .enumerate()
+        .map(|(index, code)| {
+            ppc::relative_branch_offset(*code).map(|offset| index as i64 * 4 + offset as i64)
+        })
+        .collect::<Vec<Option<i64>>>();
+
+    let is_internal = |target: i64| (0..block_len).contains(&target);
+
+    let mut result = String::new();
+    let mut has_written = false;
+
+    for (index, code) in codes.iter().enumerate() {
+        let offset = index as i64 * 4;
+
+        if targets.contains(&Some(offset)) {
+            result += &format!("label_{:X}:\n", offset);
+        }
+
+        if index >= codes.len() - num_hidden {
+            continue;
+        }
+
+        let instruction = ppc::code_to_instruction(*code);
+
+        result += &instruction_prefix(index, options);
+
+        result += &match targets[index] {
+            // the operand is the last token of the instruction
+            Some(target) if is_internal(target) => match instruction.rsplit_once(' ') {
+                Some((start, _)) => format!("{start} label_{:X}", target),
+                None => instruction
+            },
+
+            // the block's own address isn't known, so this can only be shown relative to it
+            Some(target) => {
+                let sign = if target < 0 { "-" } else { "+" };
+
+                // only `bl` and `bcl` come back
+                let kind = if code & 1 != 0 { "external call to" } else { "branch out of block to" };
+
+                format!("{instruction} // {kind} block start {sign} 0x{:X}", target.abs())
+            }
+
+            None => instruction
+        };
+
+        result += self_modification_note(*code, has_written);
+        result += &cycle_note(*code, options);
+        result += "\n";
+
+        has_written |= ppc::writes_memory(*code);
+    }
+
+    result += &cycle_total(codes, options);
+
+    result
+}
+
+/// # 0xC6: Create a Branch
+/// A branch to `target` is placed at `address`.
+/// ## Parameters
+/// `address`: The address to place the branch at.
+/// `target`: The address to branch to.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_c6(address: u32, target: u32) -> Result<String, GeckoCodeConversionError> {
+    let mut result = "// - Create a Branch -\n".to_string();
+
+    result += &format!("// Target address: 0x{:08X}\n", address);
+    result += &format!("// Branch to: 0x{:08X}\n", target);
+
+    // the code handler always writes a `b`, never a `bl`
+    match ppc::branch_instruction(address, target, false).and_then(ppc::relative_branch_offset) {
+        Some(offset) => {
+            let sign = if offset < 0 { "-" } else { "+" };
+            result += &format!("// writes: b 0x{:08X} ({sign}0x{:X})\n", target, offset.unsigned_abs());
+        }
+
+        None => result += "// Warning: the target is out of range for a single branch\n"
+    }
+
+    Ok(result)
+}
+
+/// # 0xE0: Full Terminator
+/// Ends every conditional. If they aren't 0, the upper 16 bits of
+/// the base address and pointer offset are set, and the rest are cleared.
+/// This is usually the last line of a code.
+/// ## Parameters
+/// `base_address`: The upper 16 bits that `ba` is set to, or 0 to leave it alone.
+/// `pointer_offset`: The upper 16 bits that `po` is set to, or 0 to leave it alone.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_e0(base_address: u16, pointer_offset: u16) -> Result<String, GeckoCodeConversionError> {
+    let assignments = terminator_assignments(base_address, pointer_offset);
+
+    let mut result = "// end all conditionals".to_string();
+
+    if !assignments.is_empty() {
+        result += &format!("; {assignments}");
+    }
+
+    Ok(result)
+}
+
+/// Returns how a terminator sets the base address and pointer offset,
+/// such as `ba = 0x80000000, po = 0x80000000`. This is empty if neither is set.
+fn terminator_assignments(base_address: u16, pointer_offset: u16) -> String {
+    let mut assignments: Vec<String> = Vec::new();
+
+    if base_address != 0 {
+        assignments.push(format!("ba = 0x{:04X}0000", base_address));
+    }
+
+    if pointer_offset != 0 {
+        assignments.push(format!("po = 0x{:04X}0000", pointer_offset));
+    }
+
+    assignments.join(", ")
+}
+
+/// # 0xE2: Endif
+/// Ends `count` conditionals. If the else flag is set, the conditional that
+/// was ended last is inverted instead, so the following codes run when it's false.
+/// The base address and pointer offset can be set the same as the full terminator.
+/// ## Parameters
+/// `is_else`: Indicates if the last conditional is inverted.
+/// `count`: The number of conditionals to end.
+/// `base_address`: The upper 16 bits that `ba` is set to, or 0 to leave it alone.
+/// `pointer_offset`: The upper 16 bits that `po` is set to, or 0 to leave it alone.
+/// ## Returns
+/// `Result<String, GeckoCodeConversionError>`
+fn from_e2(is_else: bool, count: u8, base_address: u16, pointer_offset: u16) -> Result<String, GeckoCodeConversionError> {
+    let assignments = terminator_assignments(base_address, pointer_offset);
+
+    // the else takes the place of ending the last conditional
+    let count = count.saturating_sub(is_else as u8);
+    let plural = if count == 1 { "" } else { "s" };
+
+    let mut lines: Vec<String> = Vec::new();
+
+    if count > 0 || !is_else {
+        lines.push(format!("// }} // end {count} conditional{plural}"));
+    }
+
+    if is_else {
+        lines.push("// } else {".to_string());
+    }
+
+    if !assignments.is_empty() {
+        lines.push(format!("// {assignments}"));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_code_spans() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x04001000, 0x00000001,
+            0xC2001000, 0x00000001,
+            0x60000000, 0x00000000,
+            0x02001000, 0x00030042
+        ])?;
+
+        assert_eq!(3, codes.len());
+
+        assert_eq!(0..2, codes[0].span);
+        assert_eq!(2..6, codes[1].span);
+        assert_eq!(6..8, codes[2].span);
+
+        assert_eq!(1, codes[0].line_number);
+        assert_eq!(2, codes[1].line_number);
+        assert_eq!(4, codes[2].line_number);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_table_of_contents() -> Result<(), GeckoCodeConversionError> {
+        let values = [
+            0x04001000, 0x00000001,
+            0xC2002000, 0x00000001,
+            0x60000000, 0x00000000,
+            0x80000003, 0x00000010
+        ];
+
+        let options = ConversionOptions {
+            table_of_contents: true,
+            ..Default::default()
+        };
+
+        let result = convert_with_options(&values, &options)?;
+
+        let expected_toc = "// - Table of Contents -\n\
+            // Line 1: 32-bit RAM Write (0x80001000)\n\
+            // Line 2: Insert Assembly (0x80002000)\n\
+            // Line 4: Set Gecko Register to\n";
+
+        assert!(result.starts_with(expected_toc));
+
+        // each entry must match the code it describes
+        for code in convert_codes(&values)? {
+            let entry = format!("// Line {}: {}", code.line_number, code_type_name(code.code_type).unwrap());
+            assert!(result.contains(&entry));
+            assert!(result.contains(&code.text));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_address_filter() -> Result<(), GeckoCodeConversionError> {
+        let values = [
+            0x04160000, 0x00000001,
+            0x04170000, 0x00000002,
+            0x0416FFFC, 0x00000003,
+            0x80000003, 0x00000010
+        ];
+
+        let options = ConversionOptions {
+            only_addresses: Some(0x80160000..0x80170000),
+            ..Default::default()
+        };
+
+        let result = convert_with_options(&values, &options)?;
+
+        assert!(result.contains("0x80160000"));
+        assert!(result.contains("0x8016FFFC"));
+        assert!(!result.contains("0x80170000"));
+        assert!(!result.contains("gr3"));
+        assert_eq!(2, result.matches("// [filtered]").count());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_gct_size() -> Result<(), GeckoCodeConversionError> {
+        // 8 bytes of code, plus the 8-byte header and 8-byte terminator
+        assert_eq!(24, gct_size(&[0x04001000, 0x00000001]));
+        assert_eq!(16, gct_size(&[]));
+
+        assert_eq!(32, gct_size_from_text("04001000 00000001\r\n04001004 00000002\r\n")?);
+        assert!(matches!(
+            gct_size_from_text("04001000 0000000G"),
+            Err(GeckoCodeConversionError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_block_instructions() -> Result<(), GeckoCodeConversionError> {
+        let instructions = block_instructions(&[
+            0xC2001000, 0x00000002,
+            0x7C0802A6, 0x38600001,
+            0x60000000, 0x00000000
+        ])?;
+
+        // the nop only pads the last line
+        assert_eq!(
+            vec![
+                (0x7C0802A6, "mflr r0".to_string(), 0),
+                (0x38600001, "li r3, 0x1".to_string(), 4)
+            ],
+            instructions
+        );
+
+        // so does the value after the return
+        let instructions = block_instructions(&[
+            0xC0000000, 0x00000002,
+            0x38600001, 0x60000000,
+            0x4E800020, 0x00000000
+        ])?;
+
+        assert_eq!(3, instructions.len());
+        assert_eq!((0x4E800020, "blr".to_string(), 8), instructions[2]);
+
+        assert!(matches!(
+            block_instructions(&[0x04001000, 0x00000001]),
+            Err(GeckoCodeConversionError::InvalidType { .. })
+        ));
+
+        Ok(())
+    }
+
+    /// Returns the contents of a GCT file containing `gecko_code`.
+    fn make_gct(gecko_code: &[u32]) -> Vec<u8> {
+        [GCT_HEADER_VALUE, GCT_HEADER_VALUE]
+            .iter()
+            .chain(gecko_code)
+            .chain(&GCT_TERMINATOR)
+            .flat_map(|value| value.to_be_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn check_gct() -> Result<(), GeckoCodeConversionError> {
+        let gct = make_gct(&[0x04001000, 0x00000001]);
+
+        assert_eq!(vec![0x04001000, 0x00000001], parse_gct(&gct)?);
+        assert!(convert_from_gct(&gct)?.contains("// Value: 0x00000001"));
+
+        // no header
+        assert!(parse_gct(&gct[8..]).is_err());
+
+        // no terminator
+        assert!(parse_gct(&gct[..gct.len() - 8]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_empty_gct() -> Result<(), GeckoCodeConversionError> {
+        let gct = make_gct(&[]);
+
+        assert_eq!(16, gct.len());
+        assert_eq!("// (no codes)", convert_from_gct(&gct)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_max_columns() -> Result<(), GeckoCodeConversionError> {
+        let values = [
+            0x84200003, 0x80001000,
+            0x06001000, 0x00000010,
+            0x01020304, 0x05060708,
+            0x090A0B0C, 0x0D0E0F10
+        ];
+
+        let options = ConversionOptions {
+            max_columns: Some(30),
+            ..Default::default()
+        };
+
+        let result = convert_with_options(&values, &options)?;
+
+        for line in result.lines() {
+            assert!(line.len() <= 30, "line is too long: {line}");
+            assert!(line.is_empty() || line.starts_with("// "));
+        }
+
+        // 30 columns fits 4 bytes per line in the byte dump
+        assert!(result.contains("\n// [0x01, 0x02, 0x03, 0x04, \n// 0x05, "));
+
+        // without a limit, the lines are left alone
+        let result = convert_from_gecko_code_values(&values)?;
+        assert!(result.lines().any(|line| line.len() > 30));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_canonicalize_output() -> Result<(), GeckoCodeConversionError> {
+        let output = convert_from_gecko_code_values(&[
+            0x04001000, 0x0000ABCD,
+            0xC2001000, 0x00000002,
+            0x7C0802A6, 0x38600001,
+            0x60000000, 0x00000000
+        ])?;
+
+        let other_output = "\
+            // - Constant 32-bit RAM Write -\r\n\
+            //   Target address: 0x80001000\r\n\
+            // Value:   0x0000abcd\r\n\
+            // =====\r\n\
+            // - Insert Assembly -\r\n\
+            // Target address: 0x80001000\r\n\
+            // note: using declared line count\r\n\
+            \r\n\
+            \tmflr   r0\r\n\
+            \tli r3,  0X1\r\n\
+            // (branch back to 0x80001004)\r\n";
+
+        assert_eq!(canonicalize_output(&output), canonicalize_output(other_output));
+
+        // differing values must still differ
+        assert_ne!(
+            canonicalize_output(&output),
+            canonicalize_output(&other_output.replace("abcd", "abce"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_store_gecko_register_address_type() -> Result<(), GeckoCodeConversionError> {
+        // 0x94 with an offset from the pointer
+        let result = convert_from_gecko_code_values(&[0x94010003, 0x00000010])?;
+        assert!(result.contains("starting at address 0x00000010 + po with"));
+
+        // 0x94 with an absolute address
+        let result = convert_from_gecko_code_values(&[0x94000003, 0x80001000])?;
+        assert!(result.contains("starting at address 0x80001000 with"));
+
+        // 0x84 with an offset from the base address
+        let result = convert_from_gecko_code_values(&[0x84010003, 0x00000010])?;
+        assert!(result.contains("starting at address 0x00000010 + ba with"));
+
+        assert!(matches!(
+            convert_from_gecko_code_values(&[0x94020003, 0x00000010]),
+            Err(GeckoCodeConversionError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_store_gecko_register_value_size() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_from_gecko_code_values(&[0x84200003, 0x80001000])?;
+        assert!(result.contains("with 1 consecutive written 4-byte values"));
+
+        let result = convert_from_gecko_code_values(&[0x84100013, 0x80001000])?;
+        assert!(result.contains("with 2 consecutive written 2-byte values"));
+
+        let result = convert_from_gecko_code_values(&[0x94000003, 0x80001000])?;
+        assert!(result.contains("with 1 consecutive written 1-byte values"));
+
+        assert!(matches!(
+            convert_from_gecko_code_values(&[0x84300003, 0x80001000]),
+            Err(GeckoCodeConversionError::ParseError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_convert_nth() -> Result<(), GeckoCodeConversionError> {
+        let values = [
+            0x04001000, 0x00000001,
+            0xC2002000, 0x00000001,
+            0x60000000, 0x00000000,
+            0x80000003, 0x00000010
+        ];
+
+        assert_eq!(convert_codes(&values)?[1].text, convert_nth(&values, 1)?);
+        assert_eq!("// gr3 = 0x00000010", convert_nth(&values, 2)?);
+
+        assert!(matches!(
+            convert_nth(&values, 3),
+            Err(GeckoCodeConversionError::IndexOutOfRange { index: 3, count: 3 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_byte_groups() -> Result<(), GeckoCodeConversionError> {
+        let values = [
+            0x06001000, 0x00000010,
+            0x01020304, 0x05060708,
+            0x090A0B0C, 0x0D0E0F10
+        ];
+
+        let options = ConversionOptions {
+            byte_group_size: Some(2),
+            ..Default::default()
+        };
+
+        let result = convert_with_options(&values, &options)?;
+
+        assert!(result.contains(
+            "// [0x01, 0x02,  0x03, 0x04,  0x05, 0x06,  0x07, 0x08, \n\
+            // 0x09, 0x0A,  0x0B, 0x0C,  0x0D, 0x0E,  0x0F, 0x10]"
+        ));
+
+        // groups are kept whole when the line width is limited
+        let options = ConversionOptions {
+            byte_group_size: Some(4),
+            max_columns: Some(40),
+            ..Default::default()
+        };
+
+        let result = convert_with_options(&values, &options)?;
+
+        assert!(result.contains(
+            "// [0x01, 0x02, 0x03, 0x04, \n\
+            // 0x05, 0x06, 0x07, 0x08, \n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_strict() -> Result<(), GeckoCodeConversionError> {
+        let options = ConversionOptions {
+            strict: true,
+            ..Default::default()
+        };
+
+        // valid codes pass either way
+        let valid = [
+            0xC2001000, 0x00000002,
+            0x7C0802A6, 0x38600001,
+            0x60000000, 0x00000000,
+            0xC0000000, 0x00000001,
+            0x38600001, 0x4E800020
+        ];
+
+        convert_with_options(&valid, &options)?;
+
+        let invalid: [&[u32]; 3] = [
+            // declares one line, but has two
+            &[
+                0xC2001000, 0x00000001,
+                0x7C0802A6, 0x38600001,
+                0x60000000, 0x00000000
+            ],
+
+            // declares more lines than there are
+            &[
+                0xC2001000, 0x00000003,
+                0x7C0802A6, 0x38600001,
+                0x60000000, 0x00000000
+            ],
+
+            // no blr
+            &[
+                0xC0000000, 0x00000001,
+                0x38600001, 0x00000000
+            ]
+        ];
+
+        for code in invalid {
+            convert_from_gecko_code_values(code)?;
+
+            assert!(matches!(
+                convert_with_options(code, &options),
+                Err(GeckoCodeConversionError::SpecViolation { line_number: 1, .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_unterminated_ascii() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_from_gecko_code_values(&[
+            0x06001000, 0x00000006,
+            0x4D617269, 0x6F210000
+        ])?;
+
+        assert!(result.contains("// ASCII (no terminator): \"Mario!\""));
+        assert!(!result.contains("// Byte contents:"));
+
+        // non-printable bytes are still dumped
+        let result = convert_from_gecko_code_values(&[
+            0x06001000, 0x00000006,
+            0x4D617269, 0x6F010000
+        ])?;
+
+        assert!(!result.contains("ASCII"));
+        assert!(result.contains("// Byte contents:"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_structured_round_trip() -> Result<(), GeckoCodeConversionError> {
+        let corpus: [&[u32]; 36] = [
+            &[0x01001000, 0x00040042],
+            &[0x02001000, 0x00030042],
+            &[0x03001000, 0xFFFF1234],
+            &[0x04001000, 0x00000001],
+            &[0x05ABCDEF, 0xDEADBEEF],
+            &[0x06001000, 0x00000006, 0x4D617269, 0x6F210000],
+            &[0x06001000, 0x0000000C, 0x01020304, 0x05060708, 0x090A0B0C, 0x00000000],
+            &[0x09001000, 0x00001234, 0x10030002, 0x00000001],
+            &[0x20001000, 0x00000001],
+            &[0x31001235, 0xDEADBEEF],
+            &[0x23001000, 0x00000000],
+            &[0x26001000, 0x00000010],
+            &[0x2B001001, 0x00FF1200],
+            &[0x3C000010, 0x00000001],
+            &[0x42011003, 0x80001000],
+            &[0x58100000, 0x00000010],
+            &[0x5C010000, 0x00000010],
+            &[0x4E00FFF8, 0x00000000],
+            &[0xAC000190, 0xFF000010],
+            &[0x80000003, 0x00000010],
+            &[0x90110002, 0x00000010],
+            &[0x86530004, 0x00000002],
+            &[0x88120003, 0x00000004],
+            &[0x60000010, 0x00000002],
+            &[0x62000000, 0x00000002],
+            &[0x64100000, 0x00000003],
+            &[0x6600FFFE, 0x00000000],
+            &[0x68200003, 0x00000001],
+            &[0xE2100001, 0x80000000],
+            &[0xE0000000, 0x80008000],
+            &[0x8C0040F3, 0x00000010],
+            &[0x82000004, 0x80001000],
+            &[0x84210013, 0x00000010],
+            &[0xC0000000, 0x00000001, 0x38600001, 0x4E800020],
+            &[0xC3001000, 0x00000002, 0x7C0802A6, 0x38600001, 0x60000000, 0x00000000],
+            &[0xC6001000, 0x80002000]
+        ];
+
+        for code in corpus {
+            let (parsed, num_values) = GeckoCode::parse(code)?;
+
+            assert_eq!(code.len(), num_values);
+            assert_eq!(code, parsed.encode()?, "{parsed:?} did not round trip");
+        }
+
+        // the whole corpus as one gecko code
+        let gecko_code = corpus.concat();
+        let codes = parse_gecko_code(&gecko_code)?;
+
+        assert_eq!(corpus.len(), codes.len());
+        assert_eq!(gecko_code, codes.iter().map(GeckoCode::encode).collect::<Result<Vec<_>, _>>()?.concat());
+
+        // the converter has to agree with the structured codes on where each code ends
+        let spans = convert_codes(&gecko_code)?
+            .into_iter()
+            .map(|code| code.span.len())
+            .collect::<Vec<usize>>();
+
+        assert_eq!(corpus.iter().map(|code| code.len()).collect::<Vec<usize>>(), spans);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_write_table() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_to_table(&[
+            0x04001000, 0x00000001,
+            0x02001004, 0x00030042,
+            0x84210013, 0x00000010,
+            0xC2002000, 0x00000001,
+            0x60000000, 0x00000000,
+            0x05001000, 0xDEADBEEF
+        ])?;
+
+        let expected = "\
+            // Address          Size  Count  Value\n\
+            // 0x80001000       4     1      0x00000001\n\
+            // 0x80001004       2     4      0x0042\n\
+            // 0x00000010 + ba  4     2      gr3\n\
+            \n\
+            // ---\n\
+            \n\
+            // - Insert Assembly -\n";
+
+        assert!(result.starts_with(expected));
+
+        // the table is restarted after the hook
+        assert!(result.contains("\
+            // Address     Size  Count  Value\n\
+            // 0x81001000  4     1      0xDEADBEEF\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_block_branches() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_from_gecko_code_values(&[
+            0xC2002000, 0x00000003,
+            0x2C030000, 0x41820008,
+            0x4BFFFF01, 0x38600001,
+            0x60000000, 0x00000000
+        ])?;
+
+        assert!(result.contains("\
+            cmpwi r3, 0x0\n\
+            beq label_C\n\
+            bl -0x100 // external call to block start - 0xF8\n\
+            label_C:\n\
+            li r3, 0x1\n"
+        ));
+
+        // branches that leave the block without linking don't come back
+        let result = convert_from_gecko_code_values(&[
+            0xC2002000, 0x00000002,
+            0x4182FF00, 0x4800010C,
+            0x60000000, 0x00000000
+        ])?;
+
+        assert!(result.contains("\
+            beq -0x100 // branch out of block to block start - 0x100\n\
+            b 0x10c // branch out of block to block start + 0x110\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_action_replay() -> Result<(), GeckoCodeConversionError> {
+        assert_eq!(
+            vec![0x04001000, 0xDEADBEEF, 0x05001000, 0x00000001, 0x02001004, 0x00030042],
+            to_action_replay(&[
+                0x04001000, 0xDEADBEEF,
+                0x05001000, 0x00000001,
+                0x02001004, 0x00030042
+            ])?
+        );
+
+        assert!(matches!(
+            to_action_replay(&[0x04001000, 0x00000001, 0xC6001000, 0x80002000]),
+            Err(GeckoCodeConversionError::NoActionReplayEquivalent { line_number: 2, code_type: 0xC6 })
+        ));
+
+        // the count of an 8-bit fill moves below the value
+        assert_eq!(vec![0x00001000, 0x00000342], to_action_replay(&[0x00001000, 0x00030042])?);
+        assert_eq!(vec![0x01001000, 0x00FFFFFF], to_action_replay(&[0x01001000, 0xFFFF00FF])?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_code_address() -> Result<(), GeckoCodeConversionError> {
+        assert_eq!(0x80001000, code_address(0x04001000, false));
+        assert_eq!(0x80FFFFFC, code_address(0x04FFFFFC, false));
+
+        // large MEM1 addresses
+        assert_eq!(0x81000000, code_address(0x05000000, true));
+        assert_eq!(0x817FFFFC, code_address(0x057FFFFC, true));
+        assert_eq!(0x05123456, encode_code_address(0x04, 0x81123456)?);
+
+        // without a base address change, the largest offset stays in MEM1 and never reaches MEM2
+        assert_eq!(0x81FFFFFC, code_address(0x05FFFFFC, true));
+        assert!(code_address(0x05FFFFFC, true) < 0x90000000);
+
+        // so a code can't target MEM2 at all
+        let code = GeckoCode::Write32 { address: 0x90001000, value: 1 };
+        assert!(matches!(code.encode(), Err(GeckoCodeConversionError::UnreachableAddress { address: 0x90001000 })));
+
+        let code = GeckoCode::If32 {
+            pointer: false,
+            comparison: Comparison::Equal,
+            endif_first: false,
+            address: 0x7FFFFFFC,
+            value: 0
+        };
+
+        assert!(matches!(code.encode(), Err(GeckoCodeConversionError::UnreachableAddress { address: 0x7FFFFFFC })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_error_context() {
+        let gecko_code = [
+            0x04001000, 0x00000001,
+            0x04001004, 0x00000002,
+            0x04001008, 0x00000003,
+            0xFF000000, 0x00000000,
+            0x0400100C, 0x00000004
+        ];
+
+        let err = convert_codes(&gecko_code).unwrap_err();
+        assert_eq!(Some(4), err.line_number());
+
+        let expected = [
+            "     2 | 04001004 00000002",
+            "     3 | 04001008 00000003",
+            ">    4 | FF000000 00000000",
+            "     5 | 0400100C 00000004"
+        ];
+
+        assert_eq!(expected.join("\n") + "\n", error_context(&gecko_code, 4, 2));
+
+        // the window is clamped to the code
+        assert!(error_context(&gecko_code, 1, 2).starts_with(">    1 |"));
+    }
+
+    #[test]
+    fn check_execute_assembly_end() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_from_gecko_code_values(&[
+            0xC0000000, 0x00000002,
+            0x7D8903A6, 0x4D820020,
+            0x4E800420, 0x00000000
+        ])?;
+
+        // the conditional return doesn't end the block
+        assert!(result.contains("mtctr r12\nbeqlr\nbctr\n"));
+        assert!(!result.contains("Warning"));
+
+        let result = convert_from_gecko_code_values(&[
+            0xC0000000, 0x00000001,
+            0x38600001, 0x60000000
+        ])?;
+
+        assert!(result.contains("li r3, 0x1\nnop\n// warning: Execute Assembly block does not end in blr\n"));
+
+        // a call through the count register comes back to the block
+        let result = convert_from_gecko_code_values(&[
+            0xC0000000, 0x00000001,
+            0x7D8903A6, 0x4E800421
+        ])?;
+
+        assert!(result.contains("mtctr r12\nbctrl\n// warning: Execute Assembly block does not end in blr\n"));
+
+        // every line is read, even after a return in the middle of the block
+        let values = [
+            0xC0000000, 0x00000002,
+            0x41820008, 0x4E800020,
+            0x38600001, 0x4E800020,
+            0x04001000, 0x00000001
+        ];
+
+        let codes = convert_codes(&values)?;
+
+        assert_eq!(2, codes.len());
+        assert!(codes[0].text.ends_with("blr\nli r3, 0x1\nblr\n"));
+        assert!(!codes[0].text.contains("warning"));
+        assert_eq!(0..6, codes[0].span);
+
+        // a return that isn't on the last line doesn't end the block
+        let result = convert_from_gecko_code_values(&[
+            0xC0000000, 0x00000002,
+            0x4E800020, 0x00000000,
+            0x38600001, 0x60000000
+        ])?;
+
+        assert!(result.contains("blr\n<illegal; found: 0x00000000>\nli r3, 0x1\nnop\n// warning: Execute Assembly block does not end in blr\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_string_write_bounds() {
+        // claims 100 bytes, but only has two values of contents
+        let result = convert_from_gecko_code_values(&[
+            0x06001000, 0x00000064,
+            0x41424344, 0x45464748
+        ]);
+
+        assert!(matches!(result, Err(GeckoCodeConversionError::Truncated { line_number: 1, opcode: 0x06 })));
+    }
+
+    #[test]
+    fn check_truncated() {
+        for (values, line_number, opcode) in [
+            (&[0x04001000, 0x00000001, 0x04001004][..], 2, 0x04),
+            (&[0x04001000][..], 1, 0x04),
+            (&[0x08001000, 0x00000001][..], 2, 0x08),
+            (&[0xC2001000, 0x00000001, 0x38600001][..], 2, 0xC2),
+
+            // a body that's cut off is reported on the line of its header
+            (&[0x04001000, 0x00000001, 0xC0000000, 0x00000002, 0x38600001, 0x4E800020][..], 2, 0xC0),
+            (&[0x06001000, 0x0000000C, 0x41424344, 0x45464748][..], 1, 0x06)
+        ] {
+            let result = convert_from_gecko_code_values(values);
+
+            assert!(
+                matches!(result, Err(GeckoCodeConversionError::Truncated { line_number: l, opcode: o }) if l == line_number && o == opcode),
+                "{result:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn check_cut_off_string_write() {
+        // claims 12 bytes, which is two lines, but only half of one is there
+        assert!(matches!(
+            GeckoCode::parse(&[0x06001000, 0x0000000C, 0x41424344]),
+            Err(GeckoCodeConversionError::Truncated { line_number: 1, opcode: 0x06 })
+        ));
+
+        // reading past the end is an error rather than a panic
+        assert!(matches!(GeckoCode::parse(&[0x06001000]), Err(GeckoCodeConversionError::Malformed)));
+    }
+
+    #[test]
+    fn check_insert_assembly_line_count() -> Result<(), GeckoCodeConversionError> {
+        // the nop in the middle is part of the subroutine
+        let values = [
+            0xC2001000, 0x00000003,
+            0x38600001, 0x60000000,
+            0x38800002, 0x60000000,
+            0x60000000, 0x00000000,
+            0x04001000, 0x00000001
+        ];
+
+        let codes = convert_codes(&values)?;
+
+        assert_eq!(2, codes.len());
+        assert_eq!(0..8, codes[0].span);
+        assert!(codes[0].text.contains("// note: using declared line count\n"));
+        assert!(codes[0].text.ends_with("li r3, 0x1\nnop\nli r4, 0x2\nnop\n// (branch back to 0x80001004)\n"));
+
+        // an instruction can share the last line with the terminator
+        let result = convert_from_gecko_code_values(&[0xC2001000, 0x00000001, 0x38600001, 0x00000000])?;
+        assert!(result.contains("// note: using declared line count\n\nli r3, 0x1\n// (branch back to 0x80001004)\n"));
+
+        // a count that can't be right falls back to finding the end
+        for num_lines in [0, 3] {
+            let result = convert_from_gecko_code_values(&[0xC2001000, num_lines, 0x38600001, 0x60000000])?;
+            assert!(result.contains("// note: heuristic termination\n\nli r3, 0x1\n// (branch back to 0x80001004)\n"), "{num_lines}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_collapse_epilogue() -> Result<(), GeckoCodeConversionError> {
+        let values = [
+            0xC2001000, 0x00000004,
+            0x9421FFF0, 0x7C0802A6,
+            0x90010014, 0x48000004,
+            0x80010014, 0x7C0803A6,
+            0x38210010, 0x00000000
+        ];
+
+        let options = ConversionOptions {
+            collapse_epilogue: true,
+            ..Default::default()
+        };
+
+        // the branch into the epilogue keeps its label
+        let result = convert_with_options(&values, &options)?;
+
+        assert!(result.contains("\
+            stwu r1, -0x10(r1)\n\
+            mflr r0\n\
+            stw r0, 0x14(r1)\n\
+            b label_10\n\
+            label_10:\n\
+            // restores LR and returns to 0x80001004\n"
+        ));
+
+        assert!(!result.contains("mtlr"));
+        assert!(!result.contains("// (branch back"));
+
+        // it's only collapsed when asked for
+        let result = convert_from_gecko_code_values(&values)?;
+        assert!(result.contains("lwz r0, 0x14(r1)\nmtlr r0\naddi r1, r1, 0x10\n// (branch back to 0x80001004)\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_insert_assembly_branch_back() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_from_gecko_code_values(&[0xC2001000, 0x00000001, 0x38600001, 0x00000000])?;
+        assert!(result.contains("li r3, 0x1\n// (branch back to 0x80001004)\n"));
+
+        // the larger address is where the branch was placed, so it's returned to as well
+        let result = convert_from_gecko_code_values(&[0xC3401000, 0x00000001, 0x38600001, 0x00000000])?;
+        assert!(result.contains("// Target address: 0x81401000\n"));
+        assert!(result.contains("li r3, 0x1\n// (branch back to 0x81401004)\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_create_branch() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_from_gecko_code_values(&[0xC6001000, 0x80002000])?;
+        assert!(result.contains("// writes: b 0x80002000 (+0x1000)\n"));
+
+        // a branch backwards has a negative displacement
+        let result = convert_from_gecko_code_values(&[0xC7401000, 0x80000C00])?;
+        assert!(result.contains("// Target address: 0x81401000\n"));
+        assert!(result.contains("// writes: b 0x80000C00 (-0x1400400)\n"));
+
+        let result = convert_from_gecko_code_values(&[0xC6001000, 0x90000000])?;
+        assert!(result.contains("// Warning: the target is out of range for a single branch\n"));
+        assert!(!result.contains("writes:"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_lone_headers() {
+        for header in [
+            [0x06001000, 0x00000008],
+            [0xC0000000, 0x00000001],
+            [0xC2001000, 0x00000001]
+        ] {
+            let result = convert_from_gecko_code_values(&header);
+            assert!(matches!(result, Err(GeckoCodeConversionError::Malformed)), "0x{:08X}", header[0]);
+        }
+    }
+
+    #[test]
+    fn check_code_titles() -> Result<(), GeckoCodeConversionError> {
+        let options = ConversionOptions {
+            code_titles: parse_code_titles("\
+                # titles for the test\n\
+                2 Moon Jump\n\
+                \n\
+                0x80001000 Infinite Lives\n"
+            )?,
+            ..Default::default()
+        };
+
+        let codes = convert_codes_with_options(&[
+            0x04001000, 0x00000001,
+            0x04001004, 0x00000002,
+            0x04001008, 0x00000003
+        ], &options)?;
+
+        assert!(codes[0].text.starts_with("// [Infinite Lives]\n// - Constant 32-bit RAM Write -"));
+        assert!(codes[1].text.starts_with("// [Moon Jump]\n"));
+        assert!(!codes[2].text.contains("// ["));
+
+        assert!(parse_code_titles("Moon Jump").is_err());
+        assert!(parse_code_titles("0xZZ Moon Jump").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_compare_regions() -> Result<(), GeckoCodeConversionError> {
+        let first = [
+            0x04001000, 0x00000001,
+            0xC6002000, 0x80003000,
+            0xC0000000, 0x00000001,
+            0x4E800020, 0x00000000
+        ];
+
+        let second = [
+            0x04001100, 0x00000001,
+            0xC6002100, 0x80003100,
+            0xC0000000, 0x00000001,
+            0x4E800020, 0x00000000
+        ];
+
+        assert!(compare_regions(&first, &second, 0x100)?.is_empty());
+
+        let differences = compare_regions(&first, &second[..4], 0x80)?;
+
+        assert_eq!(3, differences.len());
+        assert_eq!("Expected address 0x80001080, found 0x80001100.", differences[0].reason);
+        assert_eq!(1, differences[1].index);
+        assert_eq!(2, differences[2].index);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_split_named_codes() -> Result<(), GeckoCodeConversionError> {
+        let gecko_code = parse_gct(&make_gct(&[
+            0x04001000, 0x00000001,
+            0x04001004, 0x00000002,
+            0xC2002000, 0x00000001,
+            0x60000000, 0x00000000,
+            0x04001008, 0x00000003
+        ]))?;
+
+        let titles = parse_code_titles("\
+            2 Infinite Lives\n\
+            0x80001008 Moon Jump\n"
+        )?;
+
+        let codes = split_named_codes(&gecko_code, &titles)?;
+
+        assert_eq!(3, codes.len());
+
+        assert_eq!(("Unnamed".to_string(), vec![0x04001000, 0x00000001]), codes[0]);
+
+        assert_eq!("Infinite Lives", codes[1].0);
+        assert_eq!(gecko_code[2..8], codes[1].1);
+
+        assert_eq!(("Moon Jump".to_string(), vec![0x04001008, 0x00000003]), codes[2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_content_hash() -> Result<(), GeckoCodeConversionError> {
+        let options = ConversionOptions {
+            content_hash: true,
+            ..Default::default()
+        };
+
+        let codes = convert_codes_with_options(&[
+            0x04001000, 0x00000001,
+            0x04001004, 0x00000001,
+            0x04001000, 0x00000001
+        ], &options)?;
+
+        let hashes = codes
+            .iter()
+            .map(|code| code.text.lines().next().unwrap())
+            .collect::<Vec<&str>>();
+
+        assert!(hashes[0].starts_with("// Hash: "));
+        assert_eq!(hashes[0], hashes[2]);
+        assert_ne!(hashes[0], hashes[1]);
+
+        // the hash doesn't change between versions
+        assert_eq!("// Hash: eabb72de", hashes[0]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_address_delta() -> Result<(), GeckoCodeConversionError> {
+        let options = ConversionOptions {
+            address_delta: true,
+            ..Default::default()
+        };
+
+        let codes = convert_codes_with_options(&[
+            0x04001000, 0x00000001,
+            0x04001004, 0x00000002,
+            0xC6002000, 0x80003000,
+            0x02001024, 0x00010042,
+            0x84000003, 0x80001010
+        ], &options)?;
+
+        let deltas = codes
+            .iter()
+            .map(|code| code.text.lines().next().unwrap())
+            .collect::<Vec<&str>>();
+
+        // the first write has nothing to compare to, and the branch isn't a write
+        assert!(!deltas[0].starts_with("// Delta"));
+        assert_eq!("// Delta: +0x4", deltas[1]);
+        assert!(!deltas[2].starts_with("// Delta"));
+        assert_eq!("// Delta: +0x20", deltas[3]);
+        assert_eq!("// Delta: -0x14", deltas[4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_fill8() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[0x00001000, 0x00040042, 0x01001000, 0x000000FF])?;
+
+        assert_eq!("\
+            // - Constant 8-bit RAM Fill -\n\
+            // Range: 0x80001000 to 0x80001005\n\
+            // Value: 0x42",
+            codes[0].text
+        );
+
+        assert!(codes[1].text.contains("// Range: 0x81001000 to 0x81001001\n"));
+        assert_eq!(Some(0x81001000), codes[1].address);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_register_code_address() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x82200003, 0x80001000,
+            0x82210003, 0x00001000,
+            0x84200003, 0x80002000,
+            0x84210003, 0x00002000,
+            0x94210003, 0x00000010
+        ])?;
+
+        assert_eq!(Some(0x80001000), codes[0].address);
+        assert_eq!(Some(0x80002000), codes[2].address);
+
+        // the offsets are from the base address or pointer offset, which aren't known
+        assert_eq!(None, codes[1].address);
+        assert_eq!(None, codes[3].address);
+        assert_eq!(None, codes[4].address);
+
+        // the structured codes agree
+        let addresses = parse_gecko_code(&[0x82200003, 0x80001000, 0x82210003, 0x00001000])?
+            .iter()
+            .map(GeckoCode::address)
+            .collect::<Vec<Option<u32>>>();
+
+        assert_eq!(vec![Some(0x80001000), None], addresses);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_fill_range_end() -> Result<(), GeckoCodeConversionError> {
+        // a large count high in RAM doesn't wrap around
+        let result = convert_from_gecko_code_values(&[0x037FFFFF, 0xFFFF0001])?;
+        assert!(result.contains("// Range: 0x817FFFFF to 0x8180FFFF\n"));
+
+        let result = convert_from_gecko_code_values(&[0x017FFFFF, 0xFFFF0001])?;
+        assert!(result.contains("// Range: 0x817FFFFF to 0x8180FFFF\n"));
+
+        // addresses from codes can't get this high, but the end is still clamped
+        assert_eq!("// Range: 0xFFFFFFF0 to 0xFFFFFFFF // (range clamped)\n", fill_range(0xFFFFFFF0, 0xFFFF, false));
+        assert_eq!("// Range: 0xFFFFFFF0 to 0xFFFFFFFF\n", fill_range(0xFFFFFFF0, 0xE, false));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_serial_write() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x08001000, 0xFFFF1234,
+            0x10030002, 0x00000001
+        ])?;
+
+        // only the low 16 bits of the value are written
+        assert_eq!("\
+            // - Serial RAM Write -\n\
+            // Writes 4 16-bit values starting at 0x80001000, value 0x1234 incrementing by 0x1 every 0x2 bytes",
+            codes[0].text
+        );
+
+        assert_eq!(0..4, codes[0].span);
+
+        // the second line is missing
+        assert!(matches!(
+            convert_codes(&[0x08001000, 0x00000001]),
+            Err(GeckoCodeConversionError::Truncated { line_number: 2, opcode: 0x08 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_dolphin_ini() {
+        let ini = "[Gecko]\r\n\
+            $Infinite Lives [Someone]\r\n\
+            04001000 00000063\r\n\
+            *Keeps the lives counter full.\r\n\
+            $Broken Jump\r\n\
+            C2001000 00000001\r\n\
+            $Big Coins\r\n\
+            02001004 00000002\r\n\
+            [Gecko_Enabled]\r\n\
+            $Infinite Lives\r\n\
+            [ActionReplay]\r\n\
+            $Not Gecko\r\n\
+            ZZZZZZZZ 00000000\r\n";
+
+        let codes = parse_dolphin_ini(ini);
+
+        assert_eq!(3, codes.len());
+        assert_eq!(("Infinite Lives [Someone]".to_string(), "04001000 00000063\n".to_string()), codes[0]);
+        assert_eq!("Big Coins", codes[2].0);
+
+        // only the broken code is reported
+        let problems = validate_dolphin_ini(ini);
+
+        assert_eq!(1, problems.len());
+        assert_eq!("Broken Jump", problems[0].0);
+        assert!(matches!(problems[0].1, GeckoCodeConversionError::Malformed));
+    }
+
+    #[test]
+    fn check_parse_comments() -> Result<(), GeckoCodeConversionError> {
+        let text = "// Infinite Lives\r\n\
+            04001000 00000001 # lives\r\n\
+            \r\n\
+            04001004 00000002//no space\r\n";
+
+        assert_eq!(vec![0x04001000, 0x00000001, 0x04001004, 0x00000002], parse_gecko_text(text)?);
+        assert!(parse_gecko_text("// only a comment")?.is_empty());
+
+        // a comment doesn't end a group, but a blank line does
+        let text = "04001000 00000001\r\n// Part 2\r\n04001004 00000002\r\n\r\nC2002000 00000001\r\n";
+        let (values, group_starts) = parse_gecko_text_groups(text)?;
+
+        assert_eq!(6, values.len());
+        assert_eq!(vec![0, 4], group_starts);
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_convert_from_text() -> Result<(), GeckoCodeConversionError> {
+        let text = "04001000 00000001\r\n04001004 00000002\n";
+        let values = [0x04001000, 0x00000001, 0x04001004, 0x00000002];
+
+        assert_eq!(convert_from_gecko_code_values(&values)?, convert_from_gecko_text(text)?);
+
+        let Err(GeckoCodeConversionError::ParseError { reason }) = convert_from_gecko_text("04001000 0000000G") else {
+            panic!("expected a parse error");
+        };
+
+        assert!(reason.contains("\"0000000G\""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_if_equal() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x20001234, 0x00000001,
+            0x04001000, 0x00000001,
+            0x21001235, 0x00000002,
+            0x30000010, 0x00000003
+        ])?;
+
+        // the following codes are still converted on their own
+        assert_eq!(4, codes.len());
+
+        assert_eq!("\
+            // - 32-bit If Equal -\n\
+            // if (*0x80001234 == 0x00000001) {",
+            codes[0].text
+        );
+
+        assert!(codes[1].text.starts_with("  // - Constant 32-bit RAM Write -"));
+
+        assert_eq!("\
+            // - 32-bit If Equal -\n\
+            // (ends the previous if first)\n\
+            // if (*0x81001234 == 0x00000002) {",
+            codes[2].text
+        );
+
+        assert!(codes[3].text.ends_with("// if (*(po + 0x10) == 0x00000003) {"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_if_base_address() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x20001234, 0x00000001,
+            0x42000000, 0x80400000,
+            0x20001234, 0x00000001,
+            0x29001234, 0x00FF0002,
+            0x30000010, 0x00000003,
+            0xE0000000, 0x80008000,
+            0x20001234, 0x00000001
+        ])?;
+
+        // the base address starts at 0x80000000
+        assert!(codes[0].text.ends_with("// if (*0x80001234 == 0x00000001) {"));
+        assert_eq!(Some(0x80001234), codes[0].address);
+
+        // but once it's moved, only the offset from it is known
+        assert!(codes[2].text.ends_with("// if (*(ba + 0x1234) == 0x00000001) {"));
+        assert!(codes[3].text.ends_with("// if ((*(ba + 0x1001234) & ~0x00FF) == 0x0002) {"));
+        assert_eq!(None, codes[2].address);
+
+        assert!(codes[4].text.ends_with("// if (*(po + 0x10) == 0x00000003) {"));
+
+        // the terminator puts it back
+        assert!(codes[6].text.ends_with("// if (*0x80001234 == 0x00000001) {"));
+
+        // the terminator can also move it
+        let codes = convert_codes(&[
+            0xE0000000, 0x90000000,
+            0x20001234, 0x00000001
+        ])?;
+
+        assert!(codes[1].text.ends_with("// if (*(ba + 0x1234) == 0x00000001) {"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_write_base_address() -> Result<(), GeckoCodeConversionError> {
+        let gecko_code = [
+            0x04001000, 0x00000001,
+            0x42000000, 0x80400000,
+            0x04001000, 0x00000001,
+            0x00001000, 0x00030042,
+            0x06001000, 0x00000002, 0x41420000, 0x00000000,
+            0xE0000000, 0x80008000,
+            0x04001000, 0x00000001
+        ];
+
+        let codes = convert_codes(&gecko_code)?;
+
+        assert!(codes[0].text.contains("// Target address: 0x80001000\n"));
+        assert_eq!(Some(0x80001000), codes[0].address);
+
+        // once the base address has moved, writes only know their offset from it
+        assert!(codes[2].text.contains("// Target address: (ba + 0x1000)\n"));
+        assert!(codes[3].text.contains("// Range: (ba + 0x1000) to (ba + 0x1004)\n"));
+        assert!(codes[4].text.contains("// Target address: (ba + 0x1000)\n"));
+        assert!(codes[2..5].iter().all(|code| code.address.is_none()));
+
+        // the terminator puts it back
+        assert_eq!(Some(0x80001000), codes[6].address);
+
+        // and only the writes to known addresses have ranges
+        assert_eq!(vec![0x80001000..0x80001004, 0x80001000..0x80001004], write_ranges(&gecko_code)?);
+
+        // nor do they show up as absolute addresses in tables or records
+        let table = convert_to_table(&gecko_code)?;
+        assert!(table.contains("// Target address: (ba + 0x1000)\n"));
+        assert_eq!(2, table.matches("0x80001000").count());
+
+        let records = convert_to_records(&gecko_code)?;
+        assert!(records.contains("\n3\twrite32\t\t\t"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_if_not_equal() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x22001234, 0x00000001,
+            0x23001234, 0x0000FFFF
+        ])?;
+
+        assert_eq!("\
+            // - 32-bit If Not Equal -\n\
+            // if (*0x80001234 != 0x00000001) {",
+            codes[0].text
+        );
+
+        // the second is inside of the first
+        assert_eq!("\
+            \x20 // - 32-bit If Not Equal -\n\
+            \x20 // if (*0x81001234 != 0x0000FFFF) {",
+            codes[1].text
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_source_echo() -> Result<(), GeckoCodeConversionError> {
+        let options = ConversionOptions {
+            echo_source: true,
+            echo_little_endian: true,
+            ..Default::default()
+        };
+
+        let codes = convert_codes_with_options(&[0x04001000, 0x12345678], &options)?;
+
+        assert!(codes[0].text.starts_with("\
+            // Source: 04001000 12345678\n\
+            // Source (little-endian): 00100004 78563412\n\
+            // - Constant 32-bit RAM Write -"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_if_32() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x24001000, "// - 32-bit If Greater Than -\n// if (*0x80001000 > 0x00000010) { // unsigned"),
+            (0x25001000, "// - 32-bit If Greater Than -\n// if (*0x81001000 > 0x00000010) { // unsigned"),
+            (0x26001000, "// - 32-bit If Lower Than -\n// if (*0x80001000 < 0x00000010) { // unsigned"),
+            (0x27001000, "// - 32-bit If Lower Than -\n// if (*0x81001000 < 0x00000010) { // unsigned")
+        ];
+
+        for (first, expected) in table {
+            assert_eq!(expected, convert_codes(&[first, 0x00000010])?[0].text);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_if_16() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x28001000, 0x00FF1200,
+            0x2F001001, 0x00000010,
+            0x3A000010, 0xF0000123
+        ])?;
+
+        assert_eq!("\
+            // - 16-bit If Equal -\n\
+            // (the bits set in the mask are ignored)\n\
+            // if ((*0x80001000 & ~0x00FF) == 0x1200) {",
+            codes[0].text
+        );
+
+        assert_eq!("\
+            // - 16-bit If Lower Than -\n\
+            // (ends the previous if first)\n\
+            // if (*0x81001000 < 0x0010) { // unsigned",
+            codes[1].text
+        );
+
+        assert!(codes[2].text.ends_with("// if ((*(po + 0x10) & ~0xF000) != 0x0123) {"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_if_counter() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0xA8000000, 0x0000003C,
+            0xAC001290, 0xFF000010
+        ])?;
+
+        assert_eq!("\
+            // - 16-bit If Counter Equal -\n\
+            // (the counter is incremented when the condition is true)\n\
+            // Counter conditional: counter starts at 0x0000\n\
+            // if (counter == 0x003C) {",
+            codes[0].text
+        );
+
+        assert_eq!("\
+            // - 16-bit If Counter Greater Than -\n\
+            // (ends the previous if first)\n\
+            // (the counter is reset when the condition is true)\n\
+            // Counter conditional: counter starts at 0x0012\n\
+            // if ((counter & ~0xFF00) > 0x0010) { // unsigned",
+            codes[1].text
+        );
+
+        // the counter replaces the first if instead of being inside of it
+        let codes = convert_codes(&[
+            0x20001000, 0x00000001,
+            0xA8000010, 0x0000003C
+        ])?;
+
+        assert_eq!(0, codes[1].depth);
+        assert!(codes[1].text.starts_with("// - 16-bit If Counter Equal -\n// (ends the previous if first)\n"));
+
+        // the lowest bit is part of the counter
+        let codes = convert_codes(&[
+            0x20001000, 0x00000001,
+            0xA8000101, 0x0000003C
+        ])?;
+
+        assert_eq!(1, codes[1].depth);
+        assert!(codes[1].text.contains("counter starts at 0x0001"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_unreachable_conditionals() -> Result<(), GeckoCodeConversionError> {
+        let options = ConversionOptions {
+            unreachable_warnings: true,
+            ..Default::default()
+        };
+
+        let codes = convert_codes_with_options(&[
+            0x20001000, 0x00000001,
+            0x20001000, 0x00000002,
+            0x04002000, 0x00000001,
+            0x20001001, 0x00000001
+        ], &options)?;
+
+        assert!(!codes[0].text.contains("WARNING"));
+        assert!(!codes[2].text.contains("WARNING"));
+
+        // the warning is nested along with the code
+        assert_eq!("\
+            \x20 // - 32-bit If Equal -\n\
+            \x20 // if (*0x80001000 == 0x00000002) {\n\
+            \x20 // WARNING: guarded code is unreachable.",
+            codes[1].text
+        );
+
+        // the contradicting if ended first
+        assert!(!codes[3].text.contains("WARNING"));
+
+        // the value can change between the conditionals
+        let codes = convert_codes_with_options(&[
+            0x24001000, 0x00000010,
+            0x04001000, 0x00000001,
+            0x26001000, 0x00000011
+        ], &options)?;
+
+        assert!(!codes[2].text.contains("WARNING"));
+
+        let codes = convert_codes_with_options(&[
+            0x24001000, 0x00000010,
+            0x26001000, 0x00000011,
+            0x28001000, 0x00000001,
+            0x2A001000, 0x00000001
+        ], &options)?;
+
+        assert!(codes[1].text.contains("WARNING"));
+        assert!(codes[3].text.contains("WARNING"));
+
+        // setting the base address moves the value that the second if reads
+        let codes = convert_codes_with_options(&[
+            0x20001000, 0x00000001,
+            0x42000000, 0x80400000,
+            0x20001000, 0x00000002
+        ], &options)?;
+
+        assert!(codes.iter().all(|code| !code.text.contains("WARNING")));
+
+        // and so does putting the code location into it
+        let codes = convert_codes_with_options(&[
+            0x20001000, 0x00000001,
+            0x46000000, 0x00000000,
+            0x20001000, 0x00000002
+        ], &options)?;
+
+        assert!(codes.iter().all(|code| !code.text.contains("WARNING")));
+
+        Ok(())
     }
-}
 
-fn get_and_seek(cursor: &mut Cursor<&[u32]>) -> u32 {
-    let pos = cursor.position();
-    let value = cursor.get_ref()[pos as usize];
-    cursor.set_position(pos + 1);
-    value
-}
+    #[test]
+    fn check_memory_map() -> Result<(), GeckoCodeConversionError> {
+        let gecko_code = [
+            0x04001000, 0x00000001,
+            0x0200FFFE, 0x00010042,
+            0x04420000, 0x00000001,
+            0xC6001000, 0x80002000
+        ];
 
-fn get_code_address(cursor: &mut Cursor<&[u32]>, larger_address: bool) -> u32 {
-    let address = get_and_seek(cursor) & 0x00FFFFFF;
+        assert_eq!(
+            vec![0x80001000..0x80001004, 0x8000FFFE..0x80010002, 0x80420000..0x80420004],
+            write_ranges(&gecko_code)?
+        );
 
-    let final_address = 0x80000000 | address;
-    
-    if larger_address {
-        final_address + 0x01000000
-    } else {
-        final_address
-    }
-}
+        let map = memory_map(&gecko_code)?;
+        let rows = map.lines().collect::<Vec<&str>>();
 
+        // 24 MB of MEM1, at 4 MB per row
+        assert_eq!(7, rows.len());
+        assert_eq!("// MEM1 (each column is 0x10000 bytes)", rows[0]);
 
-// This is NOT a conclusive list of every type of gecko code.
-// Instead, it consists of commonly-used types.
-// Feel free to request that a code type be implemented.
+        // the fill crosses into the second cell
+        assert_eq!(format!("// 0x80000000 |##{}|", ".".repeat(62)), rows[1]);
+        assert_eq!(format!("// 0x80400000 |..#{}|", ".".repeat(61)), rows[2]);
+        assert_eq!(format!("// 0x81400000 |{}|", ".".repeat(64)), rows[6]);
 
+        Ok(())
+    }
 
-pub fn convert_from_gecko_code_values(gecko_code: &[u32]) -> Result<String, GeckoCodeConversionError> {
-    let code_length = gecko_code.len();
+    #[test]
+    fn check_set_base_address() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x42000000, "// ba = 0x80001000"),
+            (0x42100000, "// ba += 0x80001000"),
+            (0x42010000, "// ba = 0x80001000 + ba"),
+            (0x52010000, "// ba = 0x80001000 + po"),
+            (0x42101003, "// ba += 0x80001000 + gr3"),
+            (0x52011003, "// ba = 0x80001000 + po + gr3")
+        ];
 
-    // make sure the code is valid length-wise
+        for (first, expected) in table {
+            let text = convert_codes(&[first, 0x80001000])?.remove(0).text;
+            assert_eq!(format!("// - Set Base Address to -\n{expected}"), text);
+        }
 
-    if code_length == 0 {
-        return Err(GeckoCodeConversionError::Empty);
-    } else if code_length % 2 != 0 {
-        return Err(GeckoCodeConversionError::Malformed);
+        Ok(())
     }
 
-    let mut cursor = Cursor::new(gecko_code);
+    #[test]
+    fn check_self_modification_note() -> Result<(), GeckoCodeConversionError> {
+        // patch an instruction, flush it and invalidate it
+        let values = [
+            0xC2001000, 0x00000003,
+            0x90830000, 0x7C00186C,
+            0x7C0004AC, 0x7C001FAC,
+            0x4C00012C, 0x00000000
+        ];
 
-    let mut result = String::new();
+        let result = convert_from_gecko_code_values(&values)?;
 
-    let mut current_cursor_position = 0;
-    while current_cursor_position < gecko_code.len() {
-        let current_value = gecko_code[current_cursor_position];
+        assert!(result.contains("icbi r0, r3 // invalidates I-cache after self-modification\n"));
 
-        // detect code type -- this is the first byte in the code sequence
-        let byte = ((current_value & 0xFF000000) >> 0x18) as u8;
+        // nothing was written first
+        let values = [0xC0000000, 0x00000001, 0x7C001FAC, 0x4E800020];
+        let result = convert_from_gecko_code_values(&values)?;
 
-        match byte {
-            // // 8-bit RAM Write
-            // 0x00 | 0x01 => {
+        assert!(result.contains("icbi r0, r3\nblr\n"));
 
-            // }
+        Ok(())
+    }
 
-            // 16-bit RAM Write & Fill
-            0x02 | 0x03 => {
-                result += &from_02(&mut cursor, byte % 2 != 0)?;
-            }
-            
-            // 32-bit RAM Write
-            0x04 | 0x05 => {
-                result += &from_04(&mut cursor, byte % 2 != 0)?;
-            }
+    #[test]
+    fn check_instruction_numbers() -> Result<(), GeckoCodeConversionError> {
+        let values = [
+            0xC2001000, 0x00000002,
+            0x7C0802A6, 0x9421FFF0,
+            0x60000000, 0x00000000,
+            0xC2002000, 0x00000001,
+            0x38600001, 0x60000000
+        ];
 
-            // String RAM Write
-            0x06 => {
-                result += &from_06(&mut cursor, byte % 2 != 0)?;
-            }
-            
-            // Set Gecko Register to
-            0x80 => {
-                result += &from_80(&mut cursor)?;
-            }
+        let options = ConversionOptions {
+            number_instructions: true,
+            ..Default::default()
+        };
 
-            // Load into Gecko Register
-            0x82 =>  {
-                result += &from_82(&mut cursor)?;
-            }
+        let result = convert_with_options(&values, &options)?;
 
-            // Store Gecko Register at
-            0x84 | 0x94 => {
-                result += &from_84_94(&mut cursor)?;
-            }
+        assert!(result.contains("01: mflr r0\n02: stwu r1, -0x10(r1)\n"));
 
-            // Execute Assembly
-            0xC0 => {
-                result += &from_c0(&mut cursor)?;
-            }
+        // the numbers start over in the next block
+        assert!(result.contains("01: li r3, 0x1\n"));
+        assert!(!result.contains("03:"));
 
-            // Insert Assembly
-            0xC2 | 0xC3 => {
-                result += &from_c2(&mut cursor, byte % 2 != 0)?;
-            }
+        // labels aren't numbered
+        let values = [0xC2001000, 0x00000002, 0x48000008, 0x38600000, 0x38600001, 0x60000000];
+        let result = convert_with_options(&values, &options)?;
 
-            // Create a Branch
-            0xC6 | 0xC7 => {
-                result += &from_c6(&mut cursor, byte % 2 != 0)?;
-            }
+        assert!(result.contains("01: b label_8\n02: li r3, 0x0\nlabel_8:\n03: li r3, 0x1\n"));
 
-            // Invalid/Unsupported
-            _ => {
-                let err = GeckoCodeConversionError::InvalidType {
-                    line_number: (current_cursor_position / 2) + 1,
-                    value: current_value
-                };
-                
-                return Err(err);
-            }
-        }
+        let values = [0xC0000000, 0x00000001, 0x38600001, 0x4E800020];
+        let result = convert_with_options(&values, &options)?;
 
-        result += "\n\n// ---\n\n";
-        current_cursor_position = cursor.position() as usize;
+        assert!(result.contains("01: li r3, 0x1\n02: blr\n"));
+
+        Ok(())
     }
 
-    Ok(result)
-}
+    #[test]
+    fn check_cycle_estimates() -> Result<(), GeckoCodeConversionError> {
+        let instructions = [0x7C0802A6, 0x80630000, 0x7C6321D6, 0x7C6323D6];
+        let values = [
+            0xC2001000, 0x00000002,
+            instructions[0], instructions[1],
+            instructions[2], instructions[3],
+            0x60000000, 0x00000000
+        ];
 
+        let options = ConversionOptions {
+            cycle_estimates: true,
+            ..Default::default()
+        };
 
-/* Code Types */
+        let result = convert_with_options(&values, &options)?;
 
-/// # 0x00: 8-bit RAM Write & Fill
-/// The `value` will **constantly** fill the range `address`
-/// to `address + count + 1`.
-// fn from_00(cursor: &mut Cursor<&[u32]>, larger_address: bool) -> Result<String, GeckoCodeConversionError> {
-//     // let mut result = "// Constant 8-bit RAM "
-//     Ok(String::new())
-// }
+        assert!(result.contains("mullw r3, r3, r4 // ~5 cycles\n"));
 
-/// # 0x02: 16-bit RAM Write & Fill
-/// The `value` will **constantly** fill the range
-/// `address` to `address + count + 1`.
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// `larger_address`: Indicates if the given address is >= `0x01000000`.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_02(cursor: &mut Cursor<&[u32]>, larger_address: bool) -> Result<String, GeckoCodeConversionError> {
-    let mut result = "// - Constant 16-bit RAM Fill -\n".to_string();
-    let address = get_code_address(cursor, larger_address);
-    let temp = get_and_seek(cursor);
+        // the block's total is the sum of its instructions
+        let total: u32 = instructions.iter().map(|code| ppc::estimated_cycles(*code)).sum();
+        assert_eq!(29, total);
+        assert!(result.contains("divw r3, r3, r4 // ~19 cycles\n// ~29 cycles\n"));
 
-    let count = (temp & 0xFFFF0000) >> 0x10;
-    let value = (temp & 0x0000FFFF) as u16;
-    result += &format!("// Range: 0x{:08X} to 0x{:08X}\n", address, address + count + 1);
-    result += &format!("// Value: 0x{:04X}", value);
-    
-    Ok(result)
-}
+        let values = [0xC0000000, 0x00000001, 0x38600001, 0x4E800020];
+        let result = convert_with_options(&values, &options)?;
 
-/// # 0x04: 32-bit RAM Write
-/// The specified `value` will **constantly** be
-/// written to `address`.
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// `larger_address`: Indicates if the given address is >= `0x01000000`.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_04(cursor: &mut Cursor<&[u32]>, larger_address: bool) -> Result<String, GeckoCodeConversionError> {
-    let mut result = "// - Constant 32-bit RAM Write -\n".to_string();
-    result += &format!("// Target address: 0x{:08X}\n", get_code_address(cursor, larger_address));
-    result += &format!("// Value: 0x{:08X}", get_and_seek(cursor));
-    Ok(result)
-}
+        assert!(result.contains("blr // ~1 cycle\n// ~2 cycles\n"));
 
-/// # 0x06: String RAM Write
-/// The following `count` bytes will be written to `address`.
-/// ### Note
-/// The name of the code type is "String" RAM Write, but
-/// there is no null-termination check to ensure that the
-/// contents are actually a valid string. In other words,
-/// this code type can simply be used to write raw bytes,
-/// regardless of content.
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// `larger_address`: Indicates if the given address is >= `0x01000000`.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_06(cursor: &mut Cursor<&[u32]>, larger_address: bool) -> Result<String, GeckoCodeConversionError> {
-    let mut result = "// - String RAM Write - \n".to_string();
-    result += &format!("// Target address: 0x{:08X}\n", get_code_address(cursor, larger_address));
-    let num_bytes = get_and_seek(cursor);
+        Ok(())
+    }
 
-    // determine the number of values to skip
-    let num_values = (num_bytes as usize).next_multiple_of(4) / 4;
+    #[test]
+    fn check_records() -> Result<(), GeckoCodeConversionError> {
+        let gecko_code = [
+            0x04001000, 0x00000001,
+            0x00001004, 0x00030042,
+            0x20001000, 0x00000001,
+            0x80000003, 0x00000010,
+            0xC2002000, 0x00000002,
+            0x7C0802A6, 0x9421FFF0,
+            0x60000000, 0x00000000
+        ];
 
-    // read raw bytes
-    let mut raw_bytes: Vec<u8> = Vec::new();
+        assert_eq!("\
+            line\ttype\taddress\tsize\tvalue\n\
+            1\twrite32\t0x80001000\t4\t0x00000001\n\
+            2\tfill8\t0x80001004\t1\t0x42 x4\n\
+            3\tif_eq32\t0x80001000\t\tif (*0x80001000 == 0x00000001) {\n\
+            4\tset_gr\t\t\tgr3 = 0x00000010\n\
+            5\tinsert_asm\t0x80002000\t\t2 lines\n",
+            convert_to_records(&gecko_code)?
+        );
 
-    for _ in 0..num_values {
-        let value = get_and_seek(cursor);
+        // a warning on a code doesn't replace its summary
+        let records = convert_to_records(&[
+            0x20001000, 0x00000001,
+            0x60000002, 0x00000000,
+            0x64000000, 0x00000000
+        ])?;
 
-        // the bytes must be in big endian before adding
-        // them to the list
+        assert!(records.ends_with("3\treturn\t\t\treturn (block 0)\n"));
 
-        let bytes = value.to_be_bytes();
-        raw_bytes.extend(bytes);
+        Ok(())
     }
 
-    // discard extraneous values
-    raw_bytes.resize(num_bytes as usize, 0);
+    #[test]
+    fn check_conditional_tree() -> Result<(), GeckoCodeConversionError> {
+        let gecko_code = [
+            0x04001000, 0x00000001,
+            0x20001000, 0x00000001,
+            0x04002000, 0x00000001,
+            0x2A001004, 0x00000002,
+            0x00003000, 0x00000042,
+            0xE2100001, 0x00000000,
+            0x04003004, 0x00000002,
+            0xE2000001, 0x00000000,
+            0x60000002, 0x00000000,
+            0xE0000000, 0x80008000,
+            0x04004000, 0x00000003
+        ];
 
-    // determine if the bytes can be output as a string
-    // or if they should be output as-is
-    let mut is_string = false;
+        // the writes are leaves of the conditionals that guard them,
+        // and the else replaces the inner conditional
+        assert_eq!("\
+            // - 32-bit RAM Write (0x80001000)\n\
+            // if (*0x80001000 == 0x00000001)\n\
+            //   - 32-bit RAM Write (0x80002000)\n\
+            //   if (*0x80001004 != 0x0002)\n\
+            //     - 8-bit RAM Write & Fill (0x80003000)\n\
+            //   else\n\
+            //     - 32-bit RAM Write (0x80003004)\n\
+            //   - Set Repeat\n\
+            // - 32-bit RAM Write (0x80004000)\n",
+            conditional_tree(&gecko_code)?
+        );
 
-    if let Some(index) = raw_bytes
-        .iter()
-        .position(|byte| *byte == 0)
-    {
-        if !(index < raw_bytes.len() - 1) {
-            // the only 0 is at the end; this can
-            // be considered a *candidate* for 
-            // a valid string
-            is_string = true;
+        Ok(())
+    }
+
+    #[test]
+    fn check_convert_grouped() -> Result<(), GeckoCodeConversionError> {
+        let gecko_code = [
+            0x04001000, 0x00000001,
+            0x20001000, 0x00000001,
+            0xC2002000, 0x00000001,
+            0x60000000, 0x00000000,
+            0x02001004, 0x00000002
+        ];
+
+        let grouped = convert_grouped(&gecko_code)?;
+
+        assert_eq!(
+            vec![&CodeCategory::Write, &CodeCategory::Conditional, &CodeCategory::Assembly],
+            grouped.keys().collect::<Vec<_>>()
+        );
+
+        let writes = &grouped[&CodeCategory::Write];
+
+        assert_eq!(2, writes.len());
+        assert!(writes[0].starts_with("// Line 1\n"));
+        assert!(writes[1].starts_with("// Line 5\n"));
+
+        assert!(grouped[&CodeCategory::Conditional][0].starts_with("// Line 2\n// - 32-bit If Equal -"));
+        // the codes aren't nested within their groups
+        assert!(grouped[&CodeCategory::Assembly][0].starts_with("// Line 3\n// - Insert Assembly -"));
+        assert!(!grouped[&CodeCategory::Assembly][0].contains("\n  //"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_set_gecko_register() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x80000003, "// gr3 = 0x00001000"),
+            (0x80010003, "// gr3 = 0x00001000 + ba"),
+            (0x80100003, "// gr3 += 0x00001000"),
+            (0x90010003, "// gr3 = 0x00001000 + po"),
+            (0x8000000F, "// gr15 = 0x00001000")
+        ];
+
+        for (first, expected) in table {
+            assert_eq!(expected, convert_codes(&[first, 0x00001000])?[0].text);
         }
+
+        Ok(())
     }
 
-    // determine if the string was valid and printable
-    let mut printed_string = false;
+    #[test]
+    fn check_register_operation() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x86000003, 0x00000010, "// gr3 = gr3 + 0x00000010"),
+            (0x86300003, 0x0000FFFF, "// gr3 = gr3 & 0x0000FFFF"),
+            (0x86500003, 0x00000002, "// gr3 = gr3 << 0x00000002"),
+            (0x86810003, 0x00000004, "// gr3 = *gr3 >> 0x00000004 // arithmetic"),
+            (0x86020003, 0x80001000, "// gr3 = gr3 + *0x80001000")
+        ];
 
-    if is_string {
-        // try to convert it to a string
-        if let Ok(string) = String::from_utf8(raw_bytes.to_vec()) {
-            printed_string = true;
-            result += &format!("// String contents: \"{string}\"\n");
+        for (first, second, expected) in table {
+            assert_eq!(expected, convert_codes(&[first, second])?[0].text);
         }
+
+        assert!(convert_codes(&[0x86F00003, 0x00000010]).is_err());
+
+        Ok(())
     }
 
-    if !is_string || !printed_string {
-        // not a string or the string wasn't printable
-        // print out bytes instead
-        
-        result += "// Byte contents:\n// [";
+    #[test]
+    fn check_repeat() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x62000000, 0x00000001,
+            0x60000010, 0x00000000,
+            0x04001000, 0x00000001,
+            0x04001004, 0x00000002,
+            0x62000000, 0x00000000
+        ])?;
 
-        // the number of bytes that will be printed on one line
-        // before moving to the next
-        let num_printed_bytes = 8;
-        
-        for (index, byte) in raw_bytes.iter().enumerate() {
+        assert_eq!("// repeat block 1 (no repeat was set for this block)", codes[0].text);
+        assert_eq!("// set repeat: 0x10 times, block 0 (repeats from line 3)", codes[1].text);
+        assert_eq!("// repeat block 0 (back to line 3)", codes[4].text);
 
-            if index != 0 && index % num_printed_bytes == 0 {
-                result += "\n// ";
-            }
+        Ok(())
+    }
 
-            // check if this is the last one
-            if index == raw_bytes.len() - 1 {
-                result += &format!("0x{:02X}]", byte);
-            } else {
-                result += &format!("0x{:02X}, ", byte);
-            }
+    #[test]
+    fn check_goto_gosub_return() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x66000003, 0x00000000, "// goto +3 lines"),
+            (0x6600FFFE, 0x00000000, "// goto -2 lines"),
+            (0x66108000, 0x00000000, "// goto -32768 lines if the current conditional is true"),
+            (0x6800FFFE, 0x00000001, "// gosub -2 lines (block 1)"),
+            (0x68200001, 0x00000002, "// gosub +1 line (block 2) if the current conditional is false"),
+            (0x64000000, 0x00000001, "// return (block 1)")
+        ];
+
+        for (first, second, expected) in table {
+            assert_eq!(expected, convert_codes(&[first, second])?[0].text);
         }
+
+        assert!(convert_codes(&[0x66300001, 0x00000000]).is_err());
+
+        Ok(())
     }
-    
-    
-    Ok(result)
-}
 
-// /// # 0x42: Set Base Address to
-// /// ## Parameters
-// /// `cursor`: The `Cursor` for the gecko code.
-// /// ## Returns
-// /// `Result<String, GeckoCodeConversionError>`
-// fn from_42_52(cursor: &mut Cursor<&[u32]>) -> Result<String, GeckoCodeConversionError> {
-//     let code = get_and_seek(cursor);
-//     let address = get_and_seek(cursor);
+    #[test]
+    fn check_cyclic_repeat() -> Result<(), GeckoCodeConversionError> {
+        // the return goes back to the line after the set repeat, which never counts down
+        let codes = convert_codes(&[
+            0x60000002, 0x00000000,
+            0x04001000, 0x00000001,
+            0x64000000, 0x00000000
+        ])?;
 
-//     Ok(String::new())
-// }
+        assert!(!codes[1].text.contains("WARNING"));
+        assert!(codes[2].text.ends_with("\n// WARNING: this jumps back in a cycle that never runs out."));
 
+        // going back over the set repeat starts it again, which is
+        // first seen when the repeat is taken the same way as before
+        let codes = convert_codes(&[
+            0x60000002, 0x00000000,
+            0x62000000, 0x00000000,
+            0x6600FFFD, 0x00000000
+        ])?;
 
-/// # 0x80: Set Gecko Register to
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_80(cursor: &mut Cursor<&[u32]>) -> Result<String, GeckoCodeConversionError> {
-    let register = get_and_seek(cursor) & 0x000000FF;
-    let value = get_and_seek(cursor);
+        assert!(codes[1].text.contains("WARNING"));
+        assert!(!codes[2].text.contains("WARNING"));
 
-    Ok(format!("// gr{register} = 0x{:08X}", value))
-}
+        // nested repeats run out, even if there are too many of them to model
+        for count in [0x0002, 0xFFFF] {
+            let codes = convert_codes(&[
+                0x60000000 | count, 0x00000000,
+                0x60000000 | count, 0x00000001,
+                0x62000000, 0x00000001,
+                0x62000000, 0x00000000
+            ])?;
 
-/// # 0x82: Load into Gecko Register
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_82(cursor: &mut Cursor<&[u32]>) -> Result<String, GeckoCodeConversionError> {
-    let register = get_and_seek(cursor) & 0x000000FF;
-    let value = get_and_seek(cursor);
+            assert!(codes.iter().all(|code| !code.text.contains("WARNING")));
+        }
 
-    Ok(format!("// - Load value 0x{:08X} into register {register}", value))
-}
+        // a conditional return might not be taken
+        let codes = convert_codes(&[
+            0x60000002, 0x00000000,
+            0x64100000, 0x00000000
+        ])?;
 
-/// # 0x84, 0x94: Store Gecko Register at
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_84_94(cursor: &mut Cursor<&[u32]>) -> Result<String, GeckoCodeConversionError> {
-    // determine subtype
-    let code = get_and_seek(cursor);
-    let subtype = ((code & 0xFF000000) >> 0x18) as u8;    
+        assert!(!codes[1].text.contains("WARNING"));
 
-    let value_size_value = ((code & 0x00F00000) >> 0x18) as u8;
-    
-    let value_size = match value_size_value {
-        0 => 1,
-        1 => 2,
-        2 => 4,
-        _ => {
-            let err = GeckoCodeConversionError::ParseError {
-                reason: "Invalid T type. Must be 0 (1 byte), 1 (2 bytes), or 2 (4 bytes).".to_string()
-            };
+        Ok(())
+    }
 
-            return Err(err);
+    #[test]
+    fn check_full_terminator() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x80008000, "// end all conditionals; ba = 0x80000000, po = 0x80000000"),
+            (0x00000000, "// end all conditionals"),
+            (0x80000000, "// end all conditionals; ba = 0x80000000"),
+            (0x00009000, "// end all conditionals; po = 0x90000000")
+        ];
+
+        for (second, expected) in table {
+            assert_eq!(expected, convert_codes(&[0xE0000000, second])?[0].text);
         }
-    };
 
-    // the total number of consecutive written values is (num_additional_written_values + 1)
+        Ok(())
+    }
 
-    let num_additional_written_values = ((code & 0x0000FFF0) >> 0x4) as u16;
-    
-    let consecutive_written = num_additional_written_values + 1;
+    #[test]
+    fn check_endif() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0xE2000001, 0x00000000, "// } // end 1 conditional"),
+            (0xE2000003, 0x00000000, "// } // end 3 conditionals"),
+            (0xE2100001, 0x00000000, "// } else {"),
+            (0xE2100000, 0x00000000, "// } else {"),
+            (0xE2100002, 0x00000000, "// } // end 1 conditional\n// } else {"),
+            (0xE2000001, 0x80008000, "// } // end 1 conditional\n// ba = 0x80000000, po = 0x80000000")
+        ];
 
-    let register = (code & 0xF) as u8;
-    let address = get_and_seek(cursor);
-    
-    let result = match subtype {
-        0x84 => {
-            let sub_subtype = ((code & 0x000F0000) >> 0x14) as u8;
+        for (first, second, expected) in table {
+            // start inside of enough conditionals to end
+            let codes = convert_codes(&[
+                0x20001000, 0x00000000,
+                0x20001000, 0x00000000,
+                0x20001000, 0x00000000,
+                first, second
+            ])?;
 
-            match sub_subtype {
-                0 => format!("// - Store register {register} starting at address 0x{:08X} with {consecutive_written} consecutive written {value_size}-byte values -", address),
-                1 => format!("// - Store register {register} starting at address 0x{:08X} + ba with {consecutive_written} consecutive written {value_size}-byte values -", address),
+            let text = codes[3].text.lines().map(str::trim_start).collect::<Vec<&str>>().join("\n");
+            assert_eq!(expected, text);
+        }
 
-                _ => unreachable!()
-            }
+        // the else can't be true when the if was
+        let options = ConversionOptions {
+            unreachable_warnings: true,
+            ..Default::default()
+        };
 
-        }
+        let codes = convert_codes_with_options(&[
+            0x20001000, 0x00000001,
+            0xE2100001, 0x00000000,
+            0x20001000, 0x00000002,
+            0xE2000001, 0x00000000,
+            0x20001000, 0x00000002
+        ], &options)?;
 
-        0x94 => format!("// - Store register {register} starting at address 0x{:08X} + po with {consecutive_written} consecutive written {value_size}-byte values -", address),
-        _ => unreachable!()
-    };
+        assert!(!codes[2].text.contains("WARNING"));
+        assert!(!codes[4].text.contains("WARNING"));
 
-    Ok(result)
-}
+        Ok(())
+    }
 
-/// # 0xC0: Execute Assembly
-/// The following `lines` of assembly will be executed.
-/// This MUST end with a `blr` instruction (`0x4E800020`).
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_c0(cursor: &mut Cursor<&[u32]>) -> Result<String, GeckoCodeConversionError> {
-    let mut result = "// - Execute Assembly - \n".to_string();
+    #[test]
+    fn check_block_depth() -> Result<(), GeckoCodeConversionError> {
+        let result = convert_from_gecko_code_values(&[
+            0x20001000, 0x00000001,
+            0x22001004, 0x00000000,
+            0x04002000, 0x00000001,
+            0xE2000001, 0x00000000,
+            0xE2100001, 0x00000000,
+            0x80000003, 0x00000010,
+            0xE2000001, 0x00000000,
+            0x80000004, 0x00000010
+        ])?;
 
-    let address = get_and_seek(cursor);
-    result += &format!("// Target address: 0x{:08X}\n\n", address);
+        assert_eq!("\
+            // - 32-bit If Equal -\n\
+            // if (*0x80001000 == 0x00000001) {\n\n// ---\n\n\
+            \x20 // - 32-bit If Not Equal -\n\
+            \x20 // if (*0x80001004 != 0x00000000) {\n\n// ---\n\n\
+            \x20   // - Constant 32-bit RAM Write -\n\
+            \x20   // Target address: 0x80002000\n\
+            \x20   // Value: 0x00000001\n\n// ---\n\n\
+            \x20 // } // end 1 conditional\n\n// ---\n\n\
+            // } else {\n\n// ---\n\n\
+            \x20 // gr3 = 0x00000010\n\n// ---\n\n\
+            // } // end 1 conditional\n\n// ---\n\n\
+            // gr4 = 0x00000010\n\n// ---\n\n",
+            result
+        );
 
-    let num_lines = get_and_seek(cursor);
+        // there's nothing to end
+        let err = convert_codes(&[0x04001000, 0x00000001, 0xE2000001, 0x00000000]).unwrap_err();
+        assert!(matches!(err, GeckoCodeConversionError::UnbalancedBlock { line_number: 2 }));
 
-    for _ in 0..num_lines {
-        let left_code = get_and_seek(cursor);
-        let right_code = get_and_seek(cursor);
+        // the full terminator ends everything, even if nothing is open
+        let codes = convert_codes(&[0x20001000, 0x00000001, 0x20001004, 0x00000001, 0xE0000000, 0x80008000, 0xE0000000, 0x00000000])?;
 
-        if left_code == 0x4E800020 {
-            result += "blr\n";
-            break;
-        }
+        assert!(codes[1].text.starts_with("  // - 32-bit If Equal -"));
+        assert!(codes[2].text.starts_with("// end"));
+        assert!(codes[3].text.starts_with("// end"));
 
-        result += &(ppc::code_to_instruction(left_code) + "\n");
-        
-        if right_code == 0x4E800020 {
-            result += "blr\n";
-            break;
-        }
-        
-        result += &(ppc::code_to_instruction(right_code) + "\n");
+        Ok(())
     }
 
+    #[test]
+    fn check_block_depth_continuing() -> Result<(), GeckoCodeConversionError> {
+        let options = ConversionOptions::default();
+        let mut state = ConversionState::default();
 
-    Ok(result)
-}
-/// # 0xC2: Insert Assembly
-/// A branch to a subroutine containing `code` will
-/// be placed at `address`. The code must end with
-/// `0x00000000`. If an additional line must be used
-/// to do this, use a `nop` (`0x60000000`). The value
-/// stored in the second value of the first line is
-/// the total number of *subsequent* lines. **The Gecko
-/// Code handler will automatically add a branch back to
-/// `address + 0x4`.**
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// `larger_address`: Indicates if the given address is >= `0x01000000`.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_c2(cursor: &mut Cursor<&[u32]>, larger_address: bool) -> Result<String, GeckoCodeConversionError> {
-    let mut result = "// - Insert Assembly -\n".to_string();
+        let codes = convert_codes_continuing(&[0x20001000, 0x00000001], &options, &mut state)?;
+        assert_eq!(0, codes[0].depth);
+        assert_eq!(1, state.depth);
 
-    // find address
-    let address = get_code_address(cursor, larger_address);
-    result += &format!("// Target address: 0x{:08X}\n\n", address);
+        // the write is still inside of the conditional from before
+        let codes = convert_codes_continuing(&[0x04002000, 0x00000001], &options, &mut state)?;
+        assert_eq!(1, codes[0].depth);
+        assert!(codes[0].text.starts_with("  // - Constant 32-bit RAM Write -"));
 
-    let _num_lines = get_and_seek(cursor);
+        let codes = convert_codes_continuing(&[0xE2000001, 0x00000000], &options, &mut state)?;
+        assert!(codes[0].text.starts_with("// } // end 1 conditional"));
+        assert_eq!(0, state.depth);
 
-    let cursor_len = cursor.get_ref().len();
+        // a failed conversion leaves the state as it was
+        let err = convert_codes_continuing(&[0xE2000001, 0x00000000], &options, &mut state).unwrap_err();
+        assert!(matches!(err, GeckoCodeConversionError::UnbalancedBlock { line_number: 1 }));
+        assert_eq!(ConversionState::default(), state);
 
-    // process assembly
-    while (cursor.position() as usize) < cursor_len {
-        let left_code = get_and_seek(cursor);
-        let right_code = get_and_seek(cursor);
+        Ok(())
+    }
 
-        // gecko codes are written by all sorts of people
-        // and as a result don't always follow the "rules"
-        // set in place by the documentation
+    #[test]
+    fn check_memory_copy() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x8A004043, 0x00000010,
+            0x8C012034, 0x00000000,
+            0x8A0008F3, 0x80001000
+        ])?;
 
-        // by that standard, many C2 codes are "malformed", but many
-        // of these codes work regardless. sometimes these codes include
-        // invalid instructions, but they'll never be hit due to
-        // some branch being placed before they can be executed
+        // the destination is written first
+        assert_eq!("// memcpy(gr3 + 0x00000010, gr4, 0x40)", codes[0].text);
+        assert_eq!("// memcpy(gr4, gr3, 0x120)", codes[1].text);
+        assert_eq!("// memcpy(gr3 + 0x80001000, ba, 0x8)", codes[2].text);
 
-        // so, there are differing conditions in which a C2 code would end,
-        // and all of them need to be checked
+        Ok(())
+    }
 
-        // check if this is the end of the code
-        if left_code == 0x60000000 && right_code == 0 {
-            break;
+    #[test]
+    fn check_register_to_register_operation() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x88100003, "// gr3 = gr3 * gr4"),
+            (0x88110003, "// gr3 = *gr3 * gr4"),
+            (0x88120003, "// gr3 = gr3 * *gr4"),
+            (0x88130003, "// gr3 = *gr3 * *gr4"),
+            (0x88A00003, "// gr3 = gr3 * gr4 // single-precision float")
+        ];
+
+        for (first, expected) in table {
+            assert_eq!(expected, convert_codes(&[first, 0x00000004])?[0].text);
         }
 
-        result += &(ppc::code_to_instruction(left_code) + "\n");
+        Ok(())
+    }
+
+    #[test]
+    fn check_store_and_code_location() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x44000000, 0x80001000,
+            0x5C011003, 0x00000010,
+            0x46000000, 0x00000000,
+            0x4E00FFF8, 0x00000000
+        ])?;
 
-        // check if this is the end of the code
-        if right_code == 0x60000000 {
-            break;
+        assert_eq!("// - Store Base Address at -\n// *(0x80001000) = ba", codes[0].text);
+        assert_eq!("// - Store Pointer Offset at -\n// *(0x00000010 + po + gr3) = po", codes[1].text);
+
+        assert_eq!("\
+            // - Put Code Location into Base Address -\n\
+            // ba = address of next code line\n\
+            // Note: codes writing through ba can overwrite the following lines' operands at runtime",
+            codes[2].text
+        );
+
+        assert!(codes[3].text.contains("// po = address of next code line - 0x8\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_load_and_set_pointer() -> Result<(), GeckoCodeConversionError> {
+        let table = [
+            (0x40000000, "// - Load into Base Address -\n// ba = *(0x80001000)"),
+            (0x40010000, "// - Load into Base Address -\n// ba = *(0x80001000 + ba)"),
+            (0x48000000, "// - Load into Pointer Offset -\n// po = *(0x80001000)"),
+            (0x58110000, "// - Load into Pointer Offset -\n// po += *(0x80001000 + po)"),
+            (0x4A000000, "// - Set Pointer Offset to -\n// po = 0x80001000"),
+            (0x4A011002, "// - Set Pointer Offset to -\n// po = 0x80001000 + ba + gr2")
+        ];
+
+        for (first, expected) in table {
+            assert_eq!(expected, convert_codes(&[first, 0x80001000])?[0].text);
         }
 
-        result += &(ppc::code_to_instruction(right_code) + "\n");
+        Ok(())
     }
 
-    Ok(result)
-}
+    #[test]
+    fn check_string_write_padding() -> Result<(), GeckoCodeConversionError> {
+        // the 4 bytes of contents are padded to a full line
+        let codes = convert_codes(&[
+            0x06001000, 0x00000004,
+            0x41424300, 0x00000000,
+            0x04001000, 0x00000001
+        ])?;
 
-/// # 0xC6: Create a Branch
-/// A branch to `target` is placed at `address`.
-/// ## Parameters
-/// `cursor`: The `Cursor` for the gecko code.
-/// `larger_address`: Indicates if the given address is >= `0x01000000`.
-/// ## Returns
-/// `Result<String, GeckoCodeConversionError>`
-fn from_c6(cursor: &mut Cursor<&[u32]>, larger_address: bool) -> Result<String, GeckoCodeConversionError> {
-    let mut result = "// - Create a Branch -\n".to_string();
-    result += &format!("// Target address: 0x{:08X}\n", get_code_address(cursor, larger_address));
-    result += &format!("// Branch to: 0x{:08X}\n", get_and_seek(cursor));
-    Ok(result)
+        assert_eq!(2, codes.len());
+        assert_eq!(0..4, codes[0].span);
+        assert!(codes[0].text.contains("\"ABC\0\""));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_empty_string_write() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x06001000, 0x00000000,
+            0x04001000, 0x00000001
+        ])?;
+
+        assert_eq!(2, codes.len());
+        assert_eq!(0..2, codes[0].span);
+        assert!(codes[0].text.contains("// Target address: 0x80001000\n// Byte contents: (no bytes)\n"));
+        assert!(!codes[0].text.contains('['));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_string_write_larger_address() -> Result<(), GeckoCodeConversionError> {
+        let codes = convert_codes(&[
+            0x07001000, 0x00000004,
+            0x41424344, 0x00000000
+        ])?;
+
+        assert_eq!(1, codes.len());
+        assert_eq!(Some(0x81001000), codes[0].address);
+        assert!(codes[0].text.starts_with("// - String RAM Write - \n// Target address: 0x81001000\n"));
+        assert_eq!(Some("String RAM Write"), code_type_name(0x07));
+
+        Ok(())
+    }
+
+    /// Returns a minimal valid code of the given type.
+    fn minimal_code(code_type: u8) -> Vec<u32> {
+        let header = ((code_type as u32) << 0x18) | 0x00001000;
+
+        match code_type {
+            // one line of string contents
+            0x06 | 0x07 => vec![header, 8, 0x41424344, 0x45464700],
+
+            // a second line of steps
+            0x08 | 0x09 => vec![header, 0, 0, 0],
+
+            // a single blr
+            0xC0 => vec![header, 1, 0x4E800020, 0],
+
+            // a single nop followed by the terminator
+            0xC2 | 0xC3 => vec![header, 1, 0x60000000, 0],
+
+            _ => vec![header, 0]
+        }
+    }
+
+    #[test]
+    fn check_supported_types_dispatch() {
+        for (code_type, _, name) in SUPPORTED_TYPES {
+            let result = convert_from_gecko_code_values(&minimal_code(code_type));
+
+            assert!(
+                !matches!(result, Err(GeckoCodeConversionError::InvalidType { .. })),
+                "code type 0x{code_type:02X} ({name}) is not handled by the dispatch"
+            );
+        }
+    }
 }
@@ -0,0 +1,2 @@
+pub mod ppc;
+pub mod gecko;
@@ -1,24 +1,549 @@
-mod ppc;
-mod gecko;
-
 use std::fs;
-use anyhow::Result;
-use gecko::convert_from_gecko_code_values;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, bail, Result};
+use salamander::gecko::{self, convert_codes, convert_with_options, ConversionOptions, ConversionState, GeckoCode, GeckoCodeConversionError};
 
-fn main() -> Result<()> {
-    let gecko_code = fs::read_to_string("sample_codes/sample_code_3.txt")?;
+/// The input file used when none is given.
+const DEFAULT_INPUT: &str = "sample_codes/sample_code_3.txt";
+
+/// Command line arguments.
+struct Args {
+    /// The file containing the gecko code.
+    input: PathBuf,
+    /// The directory to write each converted code to, if any.
+    split_out: Option<PathBuf>,
+    /// Keep the blank-line-separated groups from the input.
+    keep_groups: bool,
+    /// Show writes as a table.
+    table: bool,
+    /// Show a map of the memory written to.
+    map: bool,
+    /// Show each code as a tab-separated record.
+    records: bool,
+    /// Show only the conditional structure as a tree.
+    tree: bool,
+    /// Wrap the output in a fenced code block, for pasting into chat or a forum.
+    fence: bool,
+    /// Treat the input as a Dolphin game INI, and report the codes that can't be converted.
+    validate_ini: bool,
+    /// A code line to identify the type of, instead of converting a file.
+    identify: Option<String>,
+    /// Convert codes from stdin as they're entered.
+    repl: bool,
+    /// The options used for conversion.
+    options: ConversionOptions
+}
+
+fn parse_args() -> Result<Args> {
+    let mut input: Option<PathBuf> = None;
+    let mut split_out: Option<PathBuf> = None;
+    let mut keep_groups = false;
+    let mut table = false;
+    let mut map = false;
+    let mut records = false;
+    let mut tree = false;
+    let mut fence = false;
+    let mut validate_ini = false;
+    let mut identify: Option<String> = None;
+    let mut repl = false;
+    let mut options = ConversionOptions::default();
+
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--split-out" => {
+                let Some(dir) = args.next() else {
+                    bail!("--split-out requires a directory");
+                };
+
+                split_out = Some(PathBuf::from(dir));
+            }
+
+            "--toc" => options.table_of_contents = true,
+
+            "--keep-groups" => keep_groups = true,
+
+            "--strict" => options.strict = true,
+
+            "--hash" => options.content_hash = true,
+
+            "--delta" => options.address_delta = true,
+
+            "--echo" => options.echo_source = true,
+
+            "--echo-le" => {
+                options.echo_source = true;
+                options.echo_little_endian = true;
+            }
+
+            "--number" => options.number_instructions = true,
+
+            "--unreachable" => options.unreachable_warnings = true,
+
+            "--cycles" => options.cycle_estimates = true,
+
+            "--collapse-epilogue" => options.collapse_epilogue = true,
+
+            "--table" => table = true,
 
-    let mut words = gecko_code.split([' ', '\n', '\r']).collect::<Vec<&str>>();
-    words.retain(|w| !w.is_empty());
+            "--map" => map = true,
+
+            "--records" => records = true,
+
+            "--tree" => tree = true,
+
+            "--fence" => fence = true,
+
+            "--validate-ini" => validate_ini = true,
+
+            "--repl" => repl = true,
+
+            "--identify" => {
+                let Some(code) = args.next() else {
+                    bail!("--identify requires a code");
+                };
+
+                identify = Some(code);
+            }
+
+            "--titles" => {
+                let Some(path) = args.next() else {
+                    bail!("--titles requires a file");
+                };
+
+                options.code_titles = gecko::parse_code_titles(&fs::read_to_string(path)?)?;
+            }
+
+            "--max-columns" => {
+                let Some(max_columns) = args.next() else {
+                    bail!("--max-columns requires a number");
+                };
+
+                options.max_columns = Some(max_columns.parse()?);
+            }
+
+            _ if arg.starts_with("--") => bail!("Unknown option: {arg}"),
+
+            _ => {
+                if input.is_some() {
+                    bail!("Only one input file may be given");
+                }
+
+                input = Some(PathBuf::from(arg));
+            }
+        }
+    }
+
+    Ok(Args {
+        input: input.unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT)),
+        split_out,
+        keep_groups,
+        table,
+        map,
+        records,
+        tree,
+        fence,
+        validate_ini,
+        identify,
+        repl,
+        options
+    })
+}
+
+/// Makes sure that the input `text` had some values in it.
+fn check_has_values(text: &str, values: &[u32]) -> Result<()> {
+    if !values.is_empty() {
+        Ok(())
+    } else if text.trim().is_empty() {
+        bail!("The input is empty");
+    } else {
+        bail!("The input contained no gecko code words (only comments/blanks)");
+    }
+}
+
+/// Converts `values`, adding a header before the first code of each group.
+/// A group that starts partway through a code is merged into the group
+/// the code started in.
+fn convert_preserving_groups(values: &[u32], group_starts: &[usize], options: &ConversionOptions) -> Result<String> {
+    let mut result = String::new();
+
+    let mut group_number = 0;
+
+    for code in gecko::convert_codes_with_options(values, options).map_err(|err| with_error_context(values, err))? {
+        let num_started = group_starts
+            .iter()
+            .filter(|start| **start < code.span.end)
+            .count();
+
+        if num_started > group_number {
+            group_number = num_started;
+            result += &format!("// === Group {group_number} ===\n\n");
+        }
+
+        result += &code.text;
+        result += "\n\n// ---\n\n";
+    }
+
+    Ok(result)
+}
+
+/// Adds the lines surrounding where `err` happened in `values` to the error, if it's known.
+fn with_error_context(values: &[u32], err: GeckoCodeConversionError) -> anyhow::Error {
+    match err.line_number() {
+        Some(line_number) => anyhow!("{err}\n\n{}", gecko::error_context(values, line_number, 2)),
+        None => err.into()
+    }
+}
+
+/// Returns the name of the code type of a code line, given as hex.
+/// Only the first word of the line is needed.
+fn identify_code_type(code: &str) -> Result<&'static str> {
+    let Some(first) = code.split_whitespace().next() else {
+        bail!("No code was given");
+    };
+
+    let value = u32::from_str_radix(first, 16)?;
+
+    Ok(gecko::code_type_name((value >> 0x18) as u8).unwrap_or("Unknown/unsupported"))
+}
+
+/// Reads lines of hex from `input`, writing the conversion of each code to `output`
+/// as soon as all of its values have been entered.
+fn run_repl(input: impl BufRead, output: &mut impl Write) -> Result<()> {
+    let options = ConversionOptions::default();
+
+    // so that codes inside of a conditional entered earlier are still nested
+    let mut state = ConversionState::default();
 
     let mut values: Vec<u32> = Vec::new();
 
-    for word in words {
-        values.push(u32::from_str_radix(word, 16)?);
+    for line in input.lines() {
+        match gecko::parse_gecko_text(&line?) {
+            Ok(line_values) => values.extend(line_values),
+            Err(err) => {
+                writeln!(output, "{err}")?;
+                continue;
+            }
+        }
+
+        // convert every code that's complete
+        while !values.is_empty() {
+            match GeckoCode::parse(&values) {
+                // the end of an Insert Assembly code is only guessed once its declared lines are all there
+                Ok((GeckoCode::InsertAssembly { num_lines, .. }, _)) if values.len() < 2 + num_lines as usize * 2 => break,
+
+                Ok((_, num_values)) => {
+                    // a code that can't be converted is dropped, and the rest are still read
+                    match gecko::convert_codes_continuing(&values[..num_values], &options, &mut state) {
+                        Ok(codes) => {
+                            for code in codes {
+                                writeln!(output, "{}\n", code.text)?;
+                            }
+                        }
+
+                        Err(err) => writeln!(output, "{}\n", with_error_context(&values[..num_values], err))?
+                    }
+
+                    values.drain(..num_values);
+                }
+
+                // wait for the rest of the code
+                Err(GeckoCodeConversionError::Malformed | GeckoCodeConversionError::Truncated { .. }) => break,
+
+                Err(err) => {
+                    writeln!(output, "{err}")?;
+                    values.clear();
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a report of the codes in a Dolphin game INI that can't be converted.
+fn validation_report(ini: &str) -> String {
+    let problems = gecko::validate_dolphin_ini(ini);
+
+    if problems.is_empty() {
+        return "All codes are valid.\n".to_string();
+    }
+
+    let mut result = format!("{} code(s) can't be converted:\n", problems.len());
+
+    for (name, err) in problems {
+        result += &format!("${name}: {err}\n");
+    }
+
+    result
+}
+
+/// Wraps `output` in a fenced code block tagged with `language`.
+fn fenced(output: &str, language: &str) -> String {
+    format!("```{language}\n{}\n```", output.trim_end())
+}
+
+/// Returns the file name for the converted code at `index` (starting from 0).
+fn split_file_name(index: usize, code_type: u8) -> String {
+    let short_name = gecko::code_type_short_name(code_type).unwrap_or("unknown");
+    format!("code_{:03}_{short_name}.txt", index + 1)
+}
+
+/// Writes each converted code to its own file in `dir`.
+fn write_split_codes(values: &[u32], dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for (index, code) in convert_codes(values)?.iter().enumerate() {
+        fs::write(dir.join(split_file_name(index, code.code_type)), format!("{}\n", code.text))?;
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = parse_args()?;
+
+    if args.repl {
+        return run_repl(io::stdin().lock(), &mut io::stdout());
+    }
+
+    if let Some(code) = &args.identify {
+        println!("{}", identify_code_type(code)?);
+        return Ok(());
+    }
+
+    if args.validate_ini {
+        print!("{}", validation_report(&fs::read_to_string(&args.input)?));
+        return Ok(());
     }
 
-    let assembly = convert_from_gecko_code_values(&values)?;
+    let output = if args.input.extension().is_some_and(|extension| extension == "gct") {
+        gecko::convert_from_gct(&fs::read(&args.input)?)?
+    } else {
+        let gecko_code = fs::read_to_string(&args.input)?;
+
+        let (values, group_starts) = gecko::parse_gecko_text_groups(&gecko_code)?;
+        check_has_values(&gecko_code, &values)?;
+
+        if let Some(dir) = &args.split_out {
+            return write_split_codes(&values, dir);
+        }
+
+        if args.table {
+            gecko::convert_to_table(&values).map_err(|err| with_error_context(&values, err))?
+        } else if args.map {
+            gecko::memory_map(&values)?
+        } else if args.records {
+            gecko::convert_to_records(&values).map_err(|err| with_error_context(&values, err))?
+        } else if args.tree {
+            gecko::conditional_tree(&values).map_err(|err| with_error_context(&values, err))?
+        } else if args.keep_groups {
+            convert_preserving_groups(&values, &group_starts, &args.options)?
+        } else {
+            convert_with_options(&values, &args.options).map_err(|err| with_error_context(&values, err))?
+        }
+    };
+
+    if args.fence {
+        // records aren't assembly
+        let language = if args.records { "" } else { "asm" };
+        println!("{}", fenced(&output, language));
+    } else if args.records {
+        print!("{output}");
+    } else {
+        println!("{output}");
+    }
 
-    println!("{assembly}");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_split_file_name() {
+        assert_eq!("code_001_write32.txt", split_file_name(0, 0x04));
+        assert_eq!("code_012_insert_asm.txt", split_file_name(11, 0xC2));
+    }
+
+    #[test]
+    fn check_identify_code_type() -> Result<()> {
+        assert_eq!("32-bit RAM Write", identify_code_type("0416B1C8")?);
+        assert_eq!("32-bit RAM Write", identify_code_type("0516B1C8 00000001")?);
+        assert_eq!("Unknown/unsupported", identify_code_type("FF000000")?);
+        assert!(identify_code_type("not hex").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_repl() -> Result<()> {
+        let input = "04001000 00000001\n\
+            C2002000 00000001\n\
+            60000000\n\
+            00000000\n\
+            FF000000 00000000\n\
+            04001004\n";
+
+        let mut output: Vec<u8> = Vec::new();
+        run_repl(input.as_bytes(), &mut output)?;
+
+        let output = String::from_utf8(output)?;
+
+        let write = output.find("0x80001000").unwrap();
+        let hook = output.find("// - Insert Assembly -").unwrap();
+        let error = output.find("Invalid gecko code type").unwrap();
+
+        // the hook is only converted once all of it has been entered,
+        // and the unfinished write at the end is never converted
+        assert!(write < hook && hook < error);
+        assert!(!output.contains("0x80001004"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_repl_conversion_error() -> Result<()> {
+        // the endif can be parsed, but there's no conditional for it to end
+        let input = "E2000001 00000000\n\
+            04001000 00000001\n";
+
+        let mut output: Vec<u8> = Vec::new();
+        run_repl(input.as_bytes(), &mut output)?;
+
+        let output = String::from_utf8(output)?;
+
+        let error = output.find("Line number: 1").unwrap();
+        let write = output.find("0x80001000").unwrap();
+
+        // the bad code is reported, and the codes after it are still converted
+        assert!(error < write);
+        assert!(output.contains(">    1 | E2000001 00000000"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_repl_nesting() -> Result<()> {
+        let input = "20001000 00000001\n\
+            04001000 00000001\n\
+            E2000001 00000000\n\
+            04001004 00000001\n";
+
+        let mut output: Vec<u8> = Vec::new();
+        run_repl(input.as_bytes(), &mut output)?;
+
+        let output = String::from_utf8(output)?;
+
+        // the endif ends the conditional entered before it
+        assert!(output.contains("\n  // Target address: 0x80001000\n"));
+        assert!(output.contains("\n// Target address: 0x80001004\n"));
+        assert!(!output.contains("Line number"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_error_context() {
+        let values = [0x04001000, 0x00000001, 0xFF000000, 0x00000000];
+
+        let err = convert_with_options(&values, &ConversionOptions::default()).unwrap_err();
+        let message = with_error_context(&values, err).to_string();
+
+        assert!(message.starts_with("Invalid gecko code type. Line number: 2"));
+        assert!(message.contains(">    2 | FF000000 00000000"));
+    }
+
+    #[test]
+    fn check_comments_only() -> Result<()> {
+        let text = "// Infinite Lives\r\n\
+            \r\n\
+            # nothing here yet\r\n";
+
+        let (values, _) = gecko::parse_gecko_text_groups(text)?;
+
+        assert_eq!(
+            "The input contained no gecko code words (only comments/blanks)",
+            check_has_values(text, &values).unwrap_err().to_string()
+        );
+
+        assert_eq!("The input is empty", check_has_values(" \r\n", &[]).unwrap_err().to_string());
+
+        let text = "04001000 00000001 // Infinite Lives\n";
+        let (values, _) = gecko::parse_gecko_text_groups(text)?;
+
+        assert_eq!(vec![0x04001000, 0x00000001], values);
+        assert!(check_has_values(text, &values).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_validation_report() {
+        let ini = "[Gecko]\n\
+            $Infinite Lives\n\
+            04001000 00000063\n\
+            $Broken Jump\n\
+            C2001000 00000001\n\
+            $Big Coins\n\
+            02001004 00000002\n";
+
+        let report = validation_report(ini);
+
+        assert!(report.starts_with("1 code(s) can't be converted:\n$Broken Jump: "));
+        assert!(!report.contains("Infinite Lives"));
+        assert!(!report.contains("Big Coins"));
+
+        assert_eq!("All codes are valid.\n", validation_report("[Gecko]\n$Infinite Lives\n04001000 00000063\n"));
+    }
+
+    #[test]
+    fn check_fenced() {
+        let output = "// gr3 = 0x00000010\n\n// ---\n\n";
+        let result = fenced(output, "asm");
+
+        assert_eq!("```asm\n// gr3 = 0x00000010\n\n// ---\n```", result);
+        assert_eq!(2, result.matches("```").count());
+        assert!(fenced("", "").starts_with("```\n"));
+    }
+
+    #[test]
+    fn check_preserved_groups() -> Result<()> {
+        let text = "04001000 00000001\r\n\
+            04001004 00000002\r\n\
+            \r\n\
+            \r\n\
+            C2002000 00000001\r\n\
+            60000000 00000000\r\n";
+
+        let (values, group_starts) = gecko::parse_gecko_text_groups(text)?;
+
+        assert_eq!(8, values.len());
+        assert_eq!(vec![0, 4], group_starts);
+
+        let result = convert_preserving_groups(&values, &group_starts, &ConversionOptions::default())?;
+
+        let first = result.find("// === Group 1 ===").unwrap();
+        let second = result.find("// === Group 2 ===").unwrap();
+
+        // both writes are in the first group, the hook is in the second
+        assert!(first < result.find("0x80001000").unwrap());
+        assert!(result.find("0x80001004").unwrap() < second);
+        assert!(second < result.find("// - Insert Assembly -").unwrap());
+        assert!(!result.contains("// === Group 3 ==="));
+
+        // the options are still used
+        let options = ConversionOptions {
+            content_hash: true,
+            ..Default::default()
+        };
+
+        let result = convert_preserving_groups(&values, &group_starts, &options)?;
+        assert_eq!(3, result.matches("// Hash: ").count());
+
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@ use ppc750cl_asm as asm;
 use thiserror::Error;
 
 /// A list of mnemonics and their argument counts.
-const EXPECTED_ARG_COUNTS: [(&str, usize); 296] = [
+const EXPECTED_ARG_COUNTS: [(&str, usize); 293] = [
     ("add", 3),
     ("addc", 3),
     ("adde", 3),
@@ -239,11 +239,8 @@ const EXPECTED_ARG_COUNTS: [(&str, usize); 296] = [
     ("bdzlr", 0),
     ("bdzt", 2),
     ("bdztlr", 1),
-    ("beq", 0),
-    ("blt", 4),
     ("clrlwi", 3),
     ("clrrwi", 3),
-    ("cmpd", 1),
     ("crmove", 2),
     ("crnot", 2),
     ("crset", 1),
@@ -349,67 +346,69 @@ fn is_offset_register_pair(token: &str) -> Result<bool, LineConversionError> {
     }
 }
 
-/// Converts a token to an i16 numeric argument.
-fn token_to_i16(token: &str) -> Result<i16, LineConversionError> {
-    // general purpose registers
-    if let Some(num) = token.strip_prefix('r') {
-        return Ok(num.parse::<i16>().unwrap()); // GPRs (rX -> X)
-    }
+/// Returns the number of a condition register bit written by name,
+/// like `eq` for `cr0` or `cr7un` for `cr7`.
+fn condition_bit(token: &str) -> Option<i64> {
+    let (field, bit) = match token.strip_prefix("cr") {
+        Some(rest) if rest.len() > 1 => (rest.get(..1)?.parse::<i64>().ok()?, rest.get(1..)?),
+        _ => (0, token)
+    };
+
+    let bit = match bit {
+        "lt" => 0,
+        "gt" => 1,
+        "eq" => 2,
+        "un" | "so" => 3,
+        _ => return None
+    };
 
-    // floating-point registers
-    if let Some(num) = token.strip_prefix('f') {
-        return Ok(num.parse::<i16>().unwrap()); // FPRs (fX -> X)
+    Some((field * 4) + bit)
+}
+
+/// Converts a token to a numeric argument.
+fn token_to_number(token: &str) -> Result<i64, LineConversionError> {
+    if let Some(bit) = condition_bit(token) {
+        return Ok(bit);
     }
 
-    let mut parsed_token = token;
+    // registers are given by their number: general purpose (rX), floating-point (fX),
+    // condition register fields (crX) and graphics quantization registers (qrX)
+    for prefix in ["cr", "qr", "r", "f"] {
+        if let Some(num) = token.strip_prefix(prefix) {
+            return num.parse::<i64>().map_err(|_| LineConversionError::InvalidNumericToken);
+        }
+    }
 
     // immediate values
-    let mult = {
-        if let Some(strip) = token.strip_prefix('-') {
-            parsed_token = strip;
-            -1
-        } else {
-            1
-        }
+    let (mult, parsed_token) = match token.strip_prefix('-') {
+        Some(strip) => (-1, strip),
+        None => (1, token)
     };
 
     // handle hex
-    if let Some(hex) = parsed_token.strip_prefix("0x") {
-        let num = i16::from_str_radix(hex, 16).unwrap();
-        return Ok(num * mult);
-    }
-
-    // handle non-hex
-    if let Ok(num) = parsed_token.parse::<i16>() {
-        return Ok(num * mult);
-    }
+    let num = match parsed_token.strip_prefix("0x") {
+        Some(hex) => i64::from_str_radix(hex, 16),
+        None => parsed_token.parse::<i64>()
+    };
 
-    
-    Err(LineConversionError::InvalidNumericToken)
+    num.map(|num| num * mult).map_err(|_| LineConversionError::InvalidNumericToken)
 }
 
-
 /// Converts a token to an assembler argument.
 fn token_to_assembler_argument(token: &str) -> Result<asm::Argument, LineConversionError> {
     // strip parens
-
-    let token = if token.contains('(') {
-        token.strip_prefix('(').unwrap()
-    } else {
-        token
-    };
-
-    let token = if token.contains(')') {
-        token.strip_prefix(')').unwrap()
-    } else {
-        token
-    };
+    let token = token.trim_start_matches('(').trim_end_matches(')');
 
     // parse
-    let arg_value = token_to_i16(token)?;
-    let arg_value = u16::from_ne_bytes(arg_value.to_ne_bytes()) as u32;
+    let arg_value = token_to_number(token)?;
 
-    Ok(asm::Argument::Unsigned(arg_value))
+    // negative values are only valid for signed fields, like offsets
+    if arg_value < 0 {
+        i32::try_from(arg_value).map(asm::Argument::Signed)
+    } else {
+        u32::try_from(arg_value).map(asm::Argument::Unsigned)
+    }
+    .map_err(|_| LineConversionError::InvalidNumericToken)
 }
 
 
@@ -420,7 +419,7 @@ fn tokens_to_assembler_arguments(tokens: &[&str]) -> Result<Vec<asm::Argument>,
     for token in tokens {
         if is_offset_register_pair(token)? {
             // split tokens
-            let left_paren_pos = token.find('(').unwrap();
+            let left_paren_pos = token.find('(').ok_or(LineConversionError::MalformedParentheses)?;
             let offset = &token[0..left_paren_pos];
             let register = &token[left_paren_pos..token.len() - 1];
             
@@ -436,8 +435,12 @@ fn tokens_to_assembler_arguments(tokens: &[&str]) -> Result<Vec<asm::Argument>,
     Ok(args)
 }
 
-/// Converts a written line of PowerPC to a u32.
+/// Converts a written line of PowerPC to a u32. Along with the instructions
+/// themselves, the simplified forms that `code_to_instruction` writes (like
+/// `li`, `mflr` and `beq`) are accepted, and any `#` note after them is skipped.
 pub fn instruction_to_code(instr: &str) -> Result<u32, LineConversionError> {
+    let instr = instr.split('#').next().unwrap_or_default();
+
     // split into individual tokens
     let mut tokens = instr.split([' ', ',']).collect::<Vec<&str>>();
 
@@ -453,7 +456,7 @@ pub fn instruction_to_code(instr: &str) -> Result<u32, LineConversionError> {
     let mnemonic = tokens.remove(0);
 
     // check if this is an instruction with no arguments
-    if tokens.len() == 0 {
+    if tokens.is_empty() {
         if let Ok(assembled) = asm::assemble(mnemonic, &[asm::Argument::None; 5]) {
             return Ok(assembled);
         }
@@ -463,8 +466,24 @@ pub fn instruction_to_code(instr: &str) -> Result<u32, LineConversionError> {
 
     let mut args = tokens_to_assembler_arguments(&tokens)?;
 
-    // validate arg count
-    if args.len() != find_arg_count(mnemonic)? {
+    // the time base registers are written without their number
+    let mnemonic = match (mnemonic, args.len()) {
+        ("mftb", 1) => {
+            args.push(asm::Argument::Unsigned(268));
+            mnemonic
+        }
+
+        ("mftbu", 1) => {
+            args.push(asm::Argument::Unsigned(269));
+            "mftb"
+        }
+
+        _ => mnemonic
+    };
+
+    // validate arg count. the simplified forms aren't listed, so
+    // the assembler checks their count instead
+    if args.len() > 5 || find_arg_count(mnemonic).is_ok_and(|count| args.len() != count) {
         return Err(LineConversionError::IncorrectArgCount);
     }
 
@@ -473,23 +492,307 @@ pub fn instruction_to_code(instr: &str) -> Result<u32, LineConversionError> {
 
     let mut passed_args = [asm::Argument::None; 5];
 
-    for i in 0..5 {
-        passed_args[i] = args[i];
+    passed_args.copy_from_slice(&args[..5]);
+
+    match asm::assemble(mnemonic, &passed_args) {
+        Ok(assembled) => Ok(assembled),
+        Err(asm::ArgumentError::ArgCount { .. }) => Err(LineConversionError::IncorrectArgCount),
+        Err(_) => Err(LineConversionError::InvalidInstruction)
     }
+}
 
-    if let Ok(assembled) = asm::assemble(mnemonic, &passed_args) {
-        Ok(assembled)
-    } else {
-        Err(LineConversionError::InvalidInstruction)
+/// Converts instructions that are valid PowerPC but aren't
+/// part of the 750CL's instruction set into a written line.
+fn code_to_extended_instruction(code: u32) -> Option<String> {
+    let opcode = code >> 26;
+    let d = (code >> 21) & 0x1F;
+    let a = (code >> 16) & 0x1F;
+    let b = (code >> 11) & 0x1F;
+
+    match opcode {
+        31 => {
+            // isel (A-form): the extended opcode is only 5 bits wide,
+            // and the CR bit to test is held where `frC` would be
+            if (code >> 1) & 0x1F == 15 {
+                let crb = disasm::CRBit(((code >> 6) & 0x1F) as u8);
+                return Some(format!("isel r{d}, r{a}, r{b}, {crb}"));
+            }
+
+            let rc = record_suffix(code);
+
+            match (code >> 1) & 0x3FF {
+                // wrtee, wrteei: these write MSR[EE] on embedded cores
+                131 => Some(format!("wrtee r{d} # privileged, embedded op, unexpected on this platform")),
+                163 => Some(format!("wrteei {} # privileged, embedded op, unexpected on this platform", (code >> 15) & 1)),
+
+                // X-form ops from later CPUs, where rA is the destination and rS is the source
+                986 => Some(format!("extsw{rc} r{a}, r{d} # 64-bit op, unexpected on this platform")),
+                58 => Some(format!("cntlzd{rc} r{a}, r{d} # 64-bit op, unexpected on this platform")),
+                570 => Some(format!("cnttzd{rc} r{a}, r{d} # 64-bit op, unexpected on this platform")),
+                506 => Some(format!("popcntd r{a}, r{d} # 64-bit op, unexpected on this platform")),
+                27 => Some(format!("sld{rc} r{a}, r{d}, r{b} # 64-bit op, unexpected on this platform")),
+                539 => Some(format!("srd{rc} r{a}, r{d}, r{b} # 64-bit op, unexpected on this platform")),
+                794 => Some(format!("srad{rc} r{a}, r{d}, r{b} # 64-bit op, unexpected on this platform")),
+                122 => Some(format!("popcntb r{a}, r{d} # newer op, unexpected on this platform")),
+                378 => Some(format!("popcntw r{a}, r{d} # newer op, unexpected on this platform")),
+                538 => Some(format!("cnttzw{rc} r{a}, r{d} # newer op, unexpected on this platform")),
+                508 => Some(format!("cmpb r{a}, r{d}, r{b} # newer op, unexpected on this platform")),
+
+                // XO-form 64-bit arithmetic, where the extended opcode is only 9 bits wide
+                xo => {
+                    let overflow = if xo & 0x200 != 0 { "o" } else { "" };
+
+                    let mnemonic = match xo & 0x1FF {
+                        233 => "mulld",
+                        489 => "divd",
+                        457 => "divdu",
+                        73 if overflow.is_empty() => "mulhd",
+                        9 if overflow.is_empty() => "mulhdu",
+                        _ => return None
+                    };
+
+                    Some(format!("{mnemonic}{overflow}{rc} r{d}, r{a}, r{b} # 64-bit op, unexpected on this platform"))
+                }
+            }
+        }
+
+        // addpcis (DX-form): the immediate is split across three fields
+        19 if (code >> 1) & 0x1F == 2 => {
+            let d0 = (code >> 6) & 0x3FF;
+            let d1 = (code >> 16) & 0x1F;
+            let d2 = code & 1;
+
+            let immediate = ((d0 << 6) | (d1 << 1) | d2) as u16 as i16;
+            let sign = if immediate < 0 { "-" } else { "" };
+
+            Some(format!("addpcis r{d}, {sign}0x{:X} # POWER9 op, unexpected on this platform", immediate.unsigned_abs()))
+        }
+
+        // ld, ldu, lwa, std, stdu (DS-form)
+        // these are 64-bit only, so this is far more likely to be data than code
+        58 | 62 => Some(format!("<illegal; found: 0x{:08X}> # 64-bit op, unexpected on this platform", code)),
+
+        _ => None
+    }
+}
+
+/// Returns the suffix for an instruction with the Rc bit set, which means it also
+/// records a summary of its result in CR0 (or CR1 for floating point instructions).
+/// Only used for instructions that aren't written by the disassembler,
+/// which already adds the suffix itself.
+fn record_suffix(code: u32) -> &'static str {
+    if code & 1 != 0 { "." } else { "" }
+}
+
+/// Converts instructions that the 750CL disassembler understands,
+/// but that have a more readable form, into a written line.
+fn code_to_refined_instruction(ins: disasm::Ins) -> Option<String> {
+    let d = (ins.code >> 21) & 0x1F;
+
+    match ins.op {
+        // time base reads
+        disasm::Opcode::Mftb | disasm::Opcode::Mfspr => {
+            // the two halves of the register number are swapped
+            let spr = ((ins.code >> 16) & 0x1F) | (((ins.code >> 11) & 0x1F) << 5);
+
+            match spr {
+                268 => Some(format!("mftb r{d}")),
+                269 => Some(format!("mftbu r{d}")),
+                _ => None
+            }
+        }
+
+        // compilers only save and restore the non-volatile registers (r14-r31) off the stack
+        // pointer, so anything else is more likely to be data than a prologue or epilogue
+        disasm::Opcode::Lmw | disasm::Opcode::Stmw => {
+            let a = (ins.code >> 16) & 0x1F;
+
+            if a != 1 || d < 14 {
+                Some(format!("{} // note: unusual lmw/stmw, may be data", ins.simplified()))
+            } else {
+                None
+            }
+        }
+
+        // these change the state of the processor
+        disasm::Opcode::Mtmsr | disasm::Opcode::Mfmsr => Some(format!("{} # privileged", ins.simplified())),
+
+        // MMU instructions are for the OS to use, so these are almost certainly data
+        disasm::Opcode::Tlbie
+        | disasm::Opcode::Tlbsync
+        | disasm::Opcode::Mfsr
+        | disasm::Opcode::Mfsrin
+        | disasm::Opcode::Mtsr
+        | disasm::Opcode::Mtsrin => Some(format!("{} # unlikely in gecko code, probably data", ins.simplified())),
+
+        // branching to CTR while also counting it down is an invalid form,
+        // so the disassembler doesn't give these a mnemonic
+        disasm::Opcode::Bcctr if d & 0x04 == 0 => {
+            let bi = disasm::CRBit(((ins.code >> 16) & 0x1F) as u8);
+
+            let counter = if d & 0x02 == 0 { "dnz" } else { "dz" };
+            let link = if ins.code & 1 != 0 { "l" } else { "" };
+
+            let result = match (d & 0x10 != 0, d & 0x08 != 0) {
+                (true, _) => format!("b{counter}ctr{link}"),
+                (false, true) => format!("b{counter}tctr{link} {bi}"),
+                (false, false) => format!("b{counter}fctr{link} {bi}")
+            };
+
+            Some(format!("{result} # invalid form, CTR can't be counted down when branching to it"))
+        }
+
+        // the operand is a bit in the FPSCR, not the CR
+        disasm::Opcode::Mtfsb0 | disasm::Opcode::Mtfsb1 => {
+            Some(format!("{}{} {d}", ins.op._mnemonic(), record_suffix(ins.code)))
+        }
+
+        _ => None
+    }
+}
+
+/// Returns the offset that a relative branch (`b`, `bl`, `bc` and its forms) jumps by.
+/// Absolute branches and other instructions return `None`.
+pub fn relative_branch_offset(code: u32) -> Option<i32> {
+    // branches with the AA bit set are absolute
+    if code & 2 != 0 {
+        return None;
+    }
+
+    match code >> 26 {
+        // I-form: a 24-bit word offset
+        18 => Some(((code & 0x03FFFFFC) << 6) as i32 >> 6),
+
+        // B-form: a 14-bit word offset
+        16 => Some((code & 0xFFFC) as u16 as i16 as i32),
+
+        _ => None
+    }
+}
+
+/// Returns the `b` instruction (or `bl` if `link` is set) that branches from
+/// `address` to `target`. Returns `None` if `target` is too far away to fit
+/// in the 26-bit displacement of a single branch.
+pub fn branch_instruction(address: u32, target: u32, link: bool) -> Option<u32> {
+    let displacement = target as i64 - address as i64;
+
+    if !(-0x02000000..0x02000000).contains(&displacement) {
+        return None;
+    }
+
+    Some(0x48000000 | (displacement as u32 & 0x03FFFFFC) | link as u32)
+}
+
+/// Returns `true` if `code` always branches to the link or count register
+/// without linking, like `blr` and `bctr`. `blrl` and `bctrl` call a function
+/// that comes back, so they don't return.
+pub fn is_unconditional_return(code: u32) -> bool {
+    let ins = disasm::Ins::new(code);
+
+    // a BO with both of these bits set ignores the condition and the counter
+    let always = (code >> 21) & 0x14 == 0x14;
+    let links = code & 1 != 0;
+
+    matches!(ins.op, disasm::Opcode::Bclr | disasm::Opcode::Bcctr) && always && !links
+}
+
+/// Returns the number of instructions at the end of `codes` that make up the
+/// standard epilogue of a function that saved LR, which loads LR back off the
+/// stack (`lwz r0, X(r1)` and `mtlr r0`) and pops the stack frame (`addi r1, r1, X`).
+/// The frame can be popped before or after LR is moved back. Returns 0 if there isn't one.
+pub fn standard_epilogue_length(codes: &[u32]) -> usize {
+    const MTLR_R0: u32 = 0x7C0803A6;
+
+    // lwz r0, X(r1)
+    let loads_lr = |code: u32| code & 0xFFFF0000 == 0x80010000;
+
+    // addi r1, r1, X, where X is positive
+    let pops_frame = |code: u32| code & 0xFFFF8000 == 0x38210000;
+
+    match codes {
+        [.., load, MTLR_R0, pop] | [.., load, pop, MTLR_R0] if loads_lr(*load) && pops_frame(*pop) => 3,
+        _ => 0
+    }
+}
+
+/// Returns `true` if `code` writes to memory, either by storing to it
+/// or by writing a data cache block back to it.
+pub fn writes_memory(code: u32) -> bool {
+    use disasm::Opcode::*;
+
+    matches!(
+        disasm::Ins::new(code).op,
+        Stb | Stbu | Stbux | Stbx
+            | Sth | Sthu | Sthux | Sthx | Sthbrx
+            | Stw | Stwu | Stwux | Stwx | Stwbrx | Stwcx_
+            | Stmw | Stswi | Stswx
+            | Stfs | Stfsu | Stfsux | Stfsx
+            | Stfd | Stfdu | Stfdux | Stfdx | Stfiwx
+            | Dcbf | Dcbst | Dcbz
+    )
+}
+
+/// Returns `true` if `code` invalidates a block of the instruction cache (`icbi`).
+pub fn is_instruction_cache_invalidate(code: u32) -> bool {
+    disasm::Ins::new(code).op == disasm::Opcode::Icbi
+}
+
+/// Returns a rough estimate of how many cycles `code` takes to execute,
+/// based on the typical latencies of the Gekko/Broadway pipeline. Cache
+/// misses and pipeline stalls aren't accounted for.
+pub fn estimated_cycles(code: u32) -> u32 {
+    use disasm::Opcode::*;
+
+    match disasm::Ins::new(code).op {
+        Illegal => 0,
+
+        Mulli => 3,
+        Mullw | Mulhw | Mulhwu => 5,
+        Divw | Divwu => 19,
+
+        // one cycle for each register, on top of a normal access
+        Lmw => 2 + (32 - ((code >> 21) & 0x1F)),
+        Stmw => 1 + (32 - ((code >> 21) & 0x1F)),
+
+        Lbz | Lbzu | Lbzux | Lbzx
+            | Lhz | Lhzu | Lhzux | Lhzx | Lha | Lhau | Lhaux | Lhax | Lhbrx
+            | Lwz | Lwzu | Lwzux | Lwzx | Lwbrx | Lwarx
+            | Lfs | Lfsu | Lfsux | Lfsx | Lfd | Lfdu | Lfdux | Lfdx
+            | PsqL | PsqLu | PsqLux | PsqLx => 2,
+
+        Fmul | Fmadd | Fmsub | Fnmadd | Fnmsub => 4,
+        Fdivs | PsDiv => 17,
+        Fdiv => 31,
+        Fres | PsRes => 10,
+
+        Fadd | Fadds | Fsub | Fsubs | Fmuls | Fmadds | Fmsubs | Fnmadds | Fnmsubs
+            | Frsp | Fctiw | Fctiwz | Fsel | Frsqrte
+            | PsAdd | PsSub | PsMul | PsMuls0 | PsMuls1 | PsMadd | PsMadds0 | PsMadds1
+            | PsMsub | PsNmadd | PsNmsub | PsSum0 | PsSum1 | PsSel | PsRsqrte => 3,
+
+        Mfspr | Mftb | Mffs | Mtfsf | Mtfsfi | Mtfsb0 | Mtfsb1 | Sync | Icbi => 3,
+        Mtspr | Isync => 2,
+
+        Dcbf | Dcbst | Dcbz | DcbzL | Dcbi => 3,
+
+        _ => 1
     }
 }
 
 /// Converts binary PowerPC into a written line.
 pub fn code_to_instruction(code: u32) -> String {
-    let result =  disasm::Ins::new(code).simplified().to_string();
+    let ins = disasm::Ins::new(code);
+
+    if let Some(result) = code_to_refined_instruction(ins) {
+        return result;
+    }
+
+    let result = ins.simplified().to_string();
 
     if result != "<illegal>" {
         result
+    } else if let Some(result) = code_to_extended_instruction(code) {
+        result
     } else {
         format!("<illegal; found: 0x{:08X}>", code)
     }
@@ -499,6 +802,29 @@ pub fn code_to_instruction(code: u32) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn check_estimated_cycles() {
+        assert_eq!(1, estimated_cycles(0x38600001)); // li r3, 0x1
+        assert_eq!(2, estimated_cycles(0x80630000)); // lwz r3, 0x0(r3)
+        assert_eq!(5, estimated_cycles(0x7C6321D6)); // mullw r3, r3, r4
+        assert_eq!(19, estimated_cycles(0x7C6323D6)); // divw r3, r3, r4
+        assert_eq!(6, estimated_cycles(0xBF610000)); // stmw r27, 0x0(r1)
+        assert_eq!(0, estimated_cycles(0x00000000));
+    }
+
+    #[test]
+    fn check_branch_instruction() {
+        assert_eq!(Some(0x48001000), branch_instruction(0x80001000, 0x80002000, false));
+        assert_eq!(Some(0x4BFFFC01), branch_instruction(0x80001000, 0x80000C00, true));
+        assert_eq!(Some(-0x400), branch_instruction(0x80001000, 0x80000C00, false).and_then(relative_branch_offset));
+
+        // the displacement is limited to 32 MiB either way
+        assert!(branch_instruction(0x80000000, 0x81FFFFFC, false).is_some());
+        assert!(branch_instruction(0x80000000, 0x82000000, false).is_none());
+        assert!(branch_instruction(0x82000000, 0x80000000, false).is_some());
+        assert!(branch_instruction(0x82000004, 0x80000000, false).is_none());
+    }
+
     #[test]
     fn check_offset_register_pair() -> Result<(), LineConversionError> {
         assert!(is_offset_register_pair("0x4(r3)")?);
@@ -512,10 +838,15 @@ mod tests {
 
     #[test]
     fn check_numeric_tokens() {
-        assert_eq!(9, token_to_i16("9").unwrap());
-        assert_eq!(16, token_to_i16("0x10").unwrap());
-        assert_eq!(-100, token_to_i16("-100").unwrap());
-        assert_eq!(-0x100, token_to_i16("-0x100").unwrap());
+        assert_eq!(9, token_to_number("9").unwrap());
+        assert_eq!(16, token_to_number("0x10").unwrap());
+        assert_eq!(-100, token_to_number("-100").unwrap());
+        assert_eq!(-0x100, token_to_number("-0x100").unwrap());
+        assert_eq!(7, token_to_number("cr7").unwrap());
+        assert_eq!(2, token_to_number("eq").unwrap());
+        assert_eq!(31, token_to_number("cr7un").unwrap());
+        assert!(token_to_number("0xZZ").is_err());
+        assert!(token_to_number("créq").is_err());
     }
 
     #[test]
@@ -553,6 +884,40 @@ mod tests {
             instruction_to_code("blr")?
         );
 
+        assert_eq!(0x38630010, instruction_to_code("addi r3, r3, 16")?);
+        assert_eq!(0x9421FFF0, instruction_to_code("stwu r1, -0x10(r1)")?);
+        assert_eq!(0x3860FFFF, instruction_to_code("li r3, -0x1")?);
+        assert_eq!(0x419E0010, instruction_to_code("beq cr7, 0x10")?);
+        assert_eq!(0x7C0004AC, instruction_to_code("sync # comment")?);
+
+        // bad input is an error, not a panic
+        assert!(matches!(instruction_to_code("lwz rX, 0x4(r3)"), Err(LineConversionError::InvalidNumericToken)));
+        assert!(matches!(instruction_to_code("li r3, 0x100000000"), Err(LineConversionError::InvalidNumericToken)));
+        assert!(matches!(instruction_to_code("li r3, 0x10000"), Err(LineConversionError::InvalidInstruction)));
+        assert!(matches!(instruction_to_code("li r3"), Err(LineConversionError::IncorrectArgCount)));
+        assert!(matches!(instruction_to_code("lwz r3, r4"), Err(LineConversionError::IncorrectArgCount)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_instruction_round_trip() -> Result<(), LineConversionError> {
+        let codes = [
+            0x38600001, 0x3863FFF0, 0x80630004, 0x9421FFF0, 0x7C0802A6, 0x7C0803A6,
+            0x4E800020, 0x4E800021, 0x4E800420, 0x60000000, 0x7C641B78, 0x48000008,
+            0x4BFFFFF8, 0x48000009, 0x41820008, 0x4082FFF8, 0x419E0010, 0x4D820020,
+            0x2C030000, 0x2803000A, 0x7C032000, 0x7C032040, 0x2F830000, 0x3C608000,
+            0x5463103A, 0x5463F0BE, 0x7C632214, 0x7C632215, 0x7C6321D6, 0x7C6323D6,
+            0x90610008, 0xBF610000, 0xC0230004, 0xD0230004, 0xFC200890, 0xEC2100B2,
+            0x10221028, 0xE0230000, 0xF0230000, 0x7C0004AC, 0x4C00012C, 0x7C001FAC,
+            0x7D8903A6, 0x7C6C42E6, 0x7C6D42E6, 0x4C221902, 0x4C1F0042, 0x7C634630
+        ];
+
+        for code in codes {
+            let instruction = code_to_instruction(code);
+            assert_eq!(code, instruction_to_code(&instruction)?, "{instruction}");
+        }
+
         Ok(())
     }
 
@@ -561,4 +926,279 @@ mod tests {
         assert!("blr".eq(&code_to_instruction(0x4E800020)));
         assert!("lwz r3, 0x4(r3)".eq(&code_to_instruction(0x80630004)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn check_isel() {
+        assert_eq!("isel r3, r4, r5, cr1eq", code_to_instruction(0x7C64299E));
+        assert_eq!("isel r3, r4, r5, lt", code_to_instruction(0x7C64281E));
+    }
+
+    #[test]
+    fn check_cache_instructions() {
+        assert_eq!("dcbz r3, r4", code_to_instruction(0x7C0327EC));
+        assert_eq!("dcbt r3, r4", code_to_instruction(0x7C03222C));
+        assert_eq!("dcbst r3, r4", code_to_instruction(0x7C03206C));
+        assert_eq!("dcbi r3, r4", code_to_instruction(0x7C0323AC));
+        assert_eq!("icbi r0, r3", code_to_instruction(0x7C001FAC));
+
+        assert!(is_instruction_cache_invalidate(0x7C001FAC));
+        assert!(!is_instruction_cache_invalidate(0x7C0323AC));
+        assert!(writes_memory(0x90640000));
+        assert!(writes_memory(0x7C0018AC));
+        assert!(!writes_memory(0x80640000));
+    }
+
+    #[test]
+    fn check_time_base_reads() {
+        assert_eq!("mftb r3", code_to_instruction(0x7C6C42E6));
+        assert_eq!("mftbu r3", code_to_instruction(0x7C6D42E6));
+
+        // mfspr with the time base register numbers
+        assert_eq!("mftb r3", code_to_instruction(0x7C6C42A6));
+    }
+
+    #[test]
+    fn check_reservation_instructions() {
+        assert_eq!("lwarx r3, r4, r5", code_to_instruction(0x7C642828));
+        assert_eq!("stwcx. r3, r4, r5", code_to_instruction(0x7C64292D));
+
+        // stwcx. must always have its Rc bit set
+        assert!(code_to_instruction(0x7C64292C).starts_with("<illegal"));
+    }
+
+    #[test]
+    fn check_carry_extend_instructions() {
+        assert_eq!("addze r3, r4", code_to_instruction(0x7C640194));
+        assert_eq!("addzeo. r3, r4", code_to_instruction(0x7C640595));
+        assert_eq!("addme r3, r4", code_to_instruction(0x7C6401D4));
+        assert_eq!("subfze r3, r4", code_to_instruction(0x7C640190));
+        assert_eq!("subfze. r3, r4", code_to_instruction(0x7C640191));
+        assert_eq!("subfmeo. r3, r4", code_to_instruction(0x7C6405D1));
+    }
+
+    #[test]
+    fn check_logical_instructions() {
+        // the destination (rA) is listed before the source (rS)
+        assert_eq!("andc r3, r4, r5", code_to_instruction(0x7C832878));
+        assert_eq!("andc. r3, r4, r5", code_to_instruction(0x7C832879));
+        assert_eq!("nand r3, r4, r5", code_to_instruction(0x7C832BB8));
+        assert_eq!("nand. r3, r4, r5", code_to_instruction(0x7C832BB9));
+        assert_eq!("orc r3, r4, r5", code_to_instruction(0x7C832B38));
+        assert_eq!("eqv r3, r4, r5", code_to_instruction(0x7C832A38));
+    }
+
+    #[test]
+    fn check_fpscr_instructions() {
+        assert_eq!("mffs f3", code_to_instruction(0xFC60048E));
+        assert_eq!("mffs. f3", code_to_instruction(0xFC60048F));
+        assert_eq!("mtfsf 255, f1", code_to_instruction(0xFDFE0D8E));
+        assert_eq!("mtfsb0 31", code_to_instruction(0xFFE0008C));
+        assert_eq!("mtfsb1. 31", code_to_instruction(0xFFE0004D));
+        assert_eq!("mcrxr cr7", code_to_instruction(0x7F800400));
+    }
+
+    #[test]
+    fn check_extended_cr_instructions() {
+        assert_eq!("crclr cr7eq", code_to_instruction(0x4FDEF182));
+        assert_eq!("crset cr7eq", code_to_instruction(0x4FDEF242));
+        assert_eq!("crmove cr1eq, eq", code_to_instruction(0x4CC21382));
+        assert_eq!("crnot cr1eq, eq", code_to_instruction(0x4CC21042));
+
+        // only use the simplified form when the operands allow it
+        assert_eq!("crxor cr1eq, eq, eq", code_to_instruction(0x4CC21182));
+    }
+
+    #[test]
+    fn check_64_bit_ops() {
+        assert_eq!(
+            "<illegal; found: 0xE8640000> # 64-bit op, unexpected on this platform",
+            code_to_instruction(0xE8640000)
+        );
+
+        assert!(code_to_instruction(0xF8640008).ends_with("# 64-bit op, unexpected on this platform"));
+    }
+
+    #[test]
+    fn check_newer_ops() {
+        assert_eq!("extsw r3, r4 # 64-bit op, unexpected on this platform", code_to_instruction(0x7C8307B4));
+        assert_eq!("extsw. r3, r4 # 64-bit op, unexpected on this platform", code_to_instruction(0x7C8307B5));
+        assert_eq!("cntlzd r3, r4 # 64-bit op, unexpected on this platform", code_to_instruction(0x7C830074));
+        assert_eq!("sld r3, r4, r5 # 64-bit op, unexpected on this platform", code_to_instruction(0x7C832836));
+        assert_eq!("mulld r3, r4, r5 # 64-bit op, unexpected on this platform", code_to_instruction(0x7C6429D2));
+        assert_eq!("divdo. r3, r4, r5 # 64-bit op, unexpected on this platform", code_to_instruction(0x7C642FD3));
+        assert_eq!("popcntw r3, r4 # newer op, unexpected on this platform", code_to_instruction(0x7C8302F4));
+        assert_eq!("cmpb r3, r4, r5 # newer op, unexpected on this platform", code_to_instruction(0x7C832BF8));
+    }
+
+    #[test]
+    fn check_relative_branch_offset() {
+        assert_eq!(Some(8), relative_branch_offset(0x48000008));
+        assert_eq!(Some(-4), relative_branch_offset(0x4BFFFFFC));
+        assert_eq!(Some(0x1000), relative_branch_offset(0x48001001));
+        assert_eq!(Some(-8), relative_branch_offset(0x4182FFF8));
+
+        // absolute branches and non-branches
+        assert_eq!(None, relative_branch_offset(0x48000003));
+        assert_eq!(None, relative_branch_offset(0x4E800020));
+        assert_eq!(None, relative_branch_offset(0x60000000));
+    }
+
+    #[test]
+    fn check_unconditional_return() {
+        assert!(is_unconditional_return(0x4E800020)); // blr
+        assert!(!is_unconditional_return(0x4E800021)); // blrl
+        assert!(is_unconditional_return(0x4E800420)); // bctr
+        assert!(!is_unconditional_return(0x4E800421)); // bctrl
+
+        assert!(!is_unconditional_return(0x4D820020)); // beqlr
+        assert!(!is_unconditional_return(0x48000008)); // b 0x8
+        assert!(!is_unconditional_return(0x60000000)); // nop
+    }
+
+    #[test]
+    fn check_standard_epilogue_length() {
+        // lwz r0, 0x14(r1); mtlr r0; addi r1, r1, 0x10
+        assert_eq!(3, standard_epilogue_length(&[0x38600001, 0x80010014, 0x7C0803A6, 0x38210010]));
+
+        // the frame can be popped first
+        assert_eq!(3, standard_epilogue_length(&[0x80010014, 0x38210010, 0x7C0803A6]));
+
+        // addi r1, r1, -0x10 pushes a frame instead
+        assert_eq!(0, standard_epilogue_length(&[0x80010014, 0x7C0803A6, 0x3821FFF0]));
+
+        // lwz r0, 0x14(r3) doesn't load from the stack
+        assert_eq!(0, standard_epilogue_length(&[0x80030014, 0x7C0803A6, 0x38210010]));
+        assert_eq!(0, standard_epilogue_length(&[0x7C0803A6, 0x38210010]));
+    }
+
+    #[test]
+    fn check_sign_extending_loads() {
+        assert_eq!("lha r3, -0x8(r4)", code_to_instruction(0xA864FFF8));
+        assert_eq!("lhau r3, 0x10(r4)", code_to_instruction(0xAC640010));
+
+        // zero-extending loads stay distinct
+        assert_eq!("lhz r3, -0x8(r4)", code_to_instruction(0xA064FFF8));
+    }
+
+    #[test]
+    fn check_multiple_word_instructions() {
+        assert_eq!("lmw r30, -0x10(r1)", code_to_instruction(0xBBC1FFF0));
+        assert_eq!("stmw r27, 0xc(r1)", code_to_instruction(0xBF61000C));
+
+        // off a register other than the stack pointer
+        assert_eq!("lmw r30, -0x10(r3) // note: unusual lmw/stmw, may be data", code_to_instruction(0xBBC3FFF0));
+
+        // too many registers
+        assert_eq!("lmw r3, 0x8(r1) // note: unusual lmw/stmw, may be data", code_to_instruction(0xB8610008));
+    }
+
+    #[test]
+    fn check_mmu_instructions() {
+        assert_eq!("tlbie r4 # unlikely in gecko code, probably data", code_to_instruction(0x7C002264));
+        assert_eq!("tlbsync # unlikely in gecko code, probably data", code_to_instruction(0x7C00046C));
+        assert_eq!("mfsr r3, 0 # unlikely in gecko code, probably data", code_to_instruction(0x7C6004A6));
+        assert_eq!("mtsrin r3, r4 # unlikely in gecko code, probably data", code_to_instruction(0x7C6021E4));
+    }
+
+    #[test]
+    fn check_branch_forms() {
+        // absolute and linking forms
+        assert_eq!("ba 0x0", code_to_instruction(0x48000002));
+        assert_eq!("bla 0x0", code_to_instruction(0x48000003));
+        assert_eq!("bnea 0x10", code_to_instruction(0x40820012));
+        assert_eq!("bnel 0x10", code_to_instruction(0x40820011));
+        assert_eq!("bnela 0x10", code_to_instruction(0x40820013));
+
+        // counter forms
+        assert_eq!("bdnz 0x10", code_to_instruction(0x42000010));
+        assert_eq!("bdz 0x10", code_to_instruction(0x42400010));
+
+        // through LR and CTR
+        assert_eq!("bnelrl", code_to_instruction(0x4C820021));
+        assert_eq!("bnectr", code_to_instruction(0x4C820420));
+        assert_eq!("bnectrl", code_to_instruction(0x4C820421));
+
+        // the rest of primary opcode 19
+        assert_eq!("mcrf cr0, cr0", code_to_instruction(0x4C000000));
+        assert_eq!("isync", code_to_instruction(0x4C00012C));
+        assert_eq!("rfi", code_to_instruction(0x4C000064));
+        assert_eq!("sc", code_to_instruction(0x44000002));
+
+        assert_eq!("addpcis r0, 0x38 # POWER9 op, unexpected on this platform", code_to_instruction(0x4C1C0004));
+        assert_eq!("addpcis r3, -0x1 # POWER9 op, unexpected on this platform", code_to_instruction(0x4C7FFFC5));
+    }
+
+    #[test]
+    fn check_float_update_instructions() {
+        // like the integer update forms, the writeback to rA is implied by the mnemonic
+        assert_eq!("lfsu f1, 0x8(r3)", code_to_instruction(0xC4230008));
+        assert_eq!("lfsu f1, -0x8(r3)", code_to_instruction(0xC423FFF8));
+        assert_eq!("stfsu f1, 0x8(r3)", code_to_instruction(0xD4230008));
+        assert_eq!("stfsu f1, -0x8(r3)", code_to_instruction(0xD423FFF8));
+    }
+
+    #[test]
+    fn check_paired_single_arithmetic() {
+        assert_eq!("ps_add f1, f2, f3", code_to_instruction(0x1022182A));
+        assert_eq!("ps_sub f1, f2, f3", code_to_instruction(0x10221828));
+        assert_eq!("ps_div f1, f2, f3", code_to_instruction(0x10221824));
+
+        // frC comes before frB
+        assert_eq!("ps_mul f1, f2, f6", code_to_instruction(0x102201B2));
+        assert_eq!("ps_madd f1, f2, f6, f4", code_to_instruction(0x102221BA));
+        assert_eq!("ps_msub f1, f2, f6, f4", code_to_instruction(0x102221B8));
+
+        assert_eq!("ps_merge00 f1, f2, f3", code_to_instruction(0x10221C20));
+        assert_eq!("ps_merge10 f1, f2, f2", code_to_instruction(0x102214A0));
+        assert_eq!("ps_merge11 f1, f2, f3", code_to_instruction(0x10221CE0));
+    }
+
+    #[test]
+    fn check_msr_instructions() {
+        assert_eq!("mtmsr r3 # privileged", code_to_instruction(0x7C600124));
+        assert_eq!("mfmsr r3 # privileged", code_to_instruction(0x7C6000A6));
+        assert_eq!("wrtee r3 # privileged, embedded op, unexpected on this platform", code_to_instruction(0x7C600106));
+        assert_eq!("wrteei 1 # privileged, embedded op, unexpected on this platform", code_to_instruction(0x7C008146));
+    }
+
+    #[test]
+    fn check_counter_branches() {
+        assert_eq!("bdnzlr", code_to_instruction(0x4E000020));
+        assert_eq!("bdzlr", code_to_instruction(0x4E400020));
+        assert_eq!("bdnzlrl", code_to_instruction(0x4E000021));
+        assert_eq!("bdnztlr eq", code_to_instruction(0x4D020020));
+
+        assert_eq!("bdnzctr # invalid form, CTR can't be counted down when branching to it", code_to_instruction(0x4E000420));
+        assert_eq!("bdzctrl # invalid form, CTR can't be counted down when branching to it", code_to_instruction(0x4E400421));
+        assert_eq!("bdnztctr eq # invalid form, CTR can't be counted down when branching to it", code_to_instruction(0x4D020420));
+
+        // the valid forms are unchanged
+        assert_eq!("bctr", code_to_instruction(0x4E800420));
+        assert_eq!("beqctr", code_to_instruction(0x4D820420));
+    }
+
+    #[test]
+    fn check_record_forms() {
+        // arithmetic
+        assert_eq!("add. r3, r3, r4", code_to_instruction(0x7C632215));
+        assert_eq!("addco. r3, r3, r4", code_to_instruction(0x7C632415));
+        assert_eq!("subf. r3, r3, r4", code_to_instruction(0x7C632051));
+        assert_eq!("mullw. r3, r3, r4", code_to_instruction(0x7C6321D7));
+        assert_eq!("neg. r3, r3", code_to_instruction(0x7C6300D1));
+
+        // logical, including simplified forms
+        assert_eq!("and. r3, r3, r4", code_to_instruction(0x7C632039));
+        assert_eq!("xor. r3, r3, r4", code_to_instruction(0x7C632279));
+        assert_eq!("mr. r3, r4", code_to_instruction(0x7C832379));
+        assert_eq!("extsh. r3, r3", code_to_instruction(0x7C630735));
+
+        // rotates and shifts
+        assert_eq!("clrlwi. r3, r3, 24", code_to_instruction(0x5463063F));
+        assert_eq!("rotlw. r3, r3, r4", code_to_instruction(0x5C63203F));
+        assert_eq!("srawi. r3, r3, 2", code_to_instruction(0x7C631671));
+
+        // floating point, which records in CR1 instead
+        assert_eq!("fadds. f1, f2, f3", code_to_instruction(0xEC22182B));
+        assert_eq!("fmr. f1, f2", code_to_instruction(0xFC201091));
+    }
+}